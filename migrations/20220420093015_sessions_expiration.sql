@@ -0,0 +1,8 @@
+ALTER TABLE sessions
+    ADD COLUMN expires_at timestamptz NOT NULL DEFAULT NOW()::timestamp;
+
+ALTER TABLE sessions
+    ADD COLUMN refresh_token TEXT UNIQUE;
+
+ALTER TABLE sessions
+    ADD COLUMN refresh_expires_at timestamptz NOT NULL DEFAULT NOW()::timestamp;
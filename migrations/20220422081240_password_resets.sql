@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS password_resets
+(
+    id         SERIAL PRIMARY KEY,
+    created_at timestamptz NOT NULL DEFAULT NOW()::timestamp,
+    user_id    INT         NOT NULL,
+    token      TEXT        NOT NULL,
+    expires_at timestamptz NOT NULL,
+    used_at    timestamptz
+);
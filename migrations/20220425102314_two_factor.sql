@@ -0,0 +1,28 @@
+ALTER TABLE users
+    ADD COLUMN totp_secret TEXT;
+
+ALTER TABLE users
+    ADD COLUMN totp_enabled BOOLEAN NOT NULL DEFAULT FALSE;
+
+ALTER TABLE users
+    ADD COLUMN totp_last_step BIGINT NOT NULL DEFAULT 0;
+
+CREATE TABLE IF NOT EXISTS recovery_codes
+(
+    id         SERIAL PRIMARY KEY,
+    created_at timestamptz NOT NULL DEFAULT NOW()::timestamp,
+    user_id    INT         NOT NULL,
+    code       TEXT        NOT NULL,
+    used_at    timestamptz
+);
+
+CREATE TABLE IF NOT EXISTS login_challenges
+(
+    id          SERIAL PRIMARY KEY,
+    created_at  timestamptz NOT NULL DEFAULT NOW()::timestamp,
+    user_id     INT         NOT NULL,
+    token       TEXT        NOT NULL UNIQUE,
+    device_name TEXT        NOT NULL,
+    attempts    INT         NOT NULL DEFAULT 0,
+    expires_at  timestamptz NOT NULL
+);
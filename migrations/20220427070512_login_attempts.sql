@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS login_attempts
+(
+    key             TEXT PRIMARY KEY,
+    failures        INT         NOT NULL DEFAULT 0,
+    last_failure_at timestamptz NOT NULL DEFAULT NOW()::timestamp,
+    locked_until    timestamptz
+);
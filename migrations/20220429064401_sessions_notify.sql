@@ -0,0 +1,28 @@
+CREATE OR REPLACE FUNCTION notify_sessions_changes() RETURNS TRIGGER AS
+$$
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+        PERFORM pg_notify('sessions_changes', json_build_object(
+                'op', 'delete',
+                'token', OLD.token
+            )::text);
+    END IF;
+
+    IF TG_OP IN ('INSERT', 'UPDATE') THEN
+        PERFORM pg_notify('sessions_changes', json_build_object(
+                'op', 'insert',
+                'token', NEW.token,
+                'user_id', NEW.user_id,
+                'expires_at', EXTRACT(EPOCH FROM NEW.expires_at)::BIGINT
+            )::text);
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER sessions_changes
+    AFTER INSERT OR UPDATE OR DELETE
+    ON sessions
+    FOR EACH ROW
+EXECUTE PROCEDURE notify_sessions_changes();
@@ -0,0 +1,45 @@
+CREATE TABLE IF NOT EXISTS revoked_tokens
+(
+    jti        TEXT PRIMARY KEY,
+    expires_at timestamptz NOT NULL
+);
+
+CREATE OR REPLACE FUNCTION revoke_sessions_tokens() RETURNS TRIGGER AS
+$$
+BEGIN
+    IF TG_OP = 'DELETE' OR OLD.token <> NEW.token THEN
+        DELETE FROM revoked_tokens WHERE expires_at <= NOW();
+
+        INSERT INTO revoked_tokens (jti, expires_at)
+        VALUES (OLD.token, OLD.expires_at)
+        ON CONFLICT DO NOTHING;
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER sessions_revoke_tokens
+    AFTER UPDATE OR DELETE
+    ON sessions
+    FOR EACH ROW
+EXECUTE PROCEDURE revoke_sessions_tokens();
+
+CREATE OR REPLACE FUNCTION notify_revoked_tokens() RETURNS TRIGGER AS
+$$
+BEGIN
+    PERFORM pg_notify('sessions_changes', json_build_object(
+            'op', 'revoke',
+            'jti', NEW.jti,
+            'expires_at', EXTRACT(EPOCH FROM NEW.expires_at)::BIGINT
+        )::text);
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER revoked_tokens_changes
+    AFTER INSERT
+    ON revoked_tokens
+    FOR EACH ROW
+EXECUTE PROCEDURE notify_revoked_tokens();
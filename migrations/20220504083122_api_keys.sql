@@ -0,0 +1,40 @@
+CREATE TABLE IF NOT EXISTS api_keys
+(
+    id         SERIAL PRIMARY KEY,
+    created_at timestamptz NOT NULL DEFAULT NOW()::timestamp,
+    updated_at timestamptz NOT NULL DEFAULT NOW()::timestamp,
+    user_id    INT         NOT NULL,
+    name       TEXT        NOT NULL,
+    prefix     TEXT        NOT NULL,
+    key_hash   TEXT        NOT NULL UNIQUE,
+    scopes     TEXT[]      NOT NULL DEFAULT '{}'
+);
+
+CREATE OR REPLACE FUNCTION notify_api_keys_changes() RETURNS TRIGGER AS
+$$
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+        PERFORM pg_notify('sessions_changes', json_build_object(
+                'op', 'api_key_delete',
+                'key_hash', OLD.key_hash
+            )::text);
+    END IF;
+
+    IF TG_OP IN ('INSERT', 'UPDATE') THEN
+        PERFORM pg_notify('sessions_changes', json_build_object(
+                'op', 'api_key_insert',
+                'key_hash', NEW.key_hash,
+                'user_id', NEW.user_id,
+                'scopes', NEW.scopes
+            )::text);
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER api_keys_changes
+    AFTER INSERT OR UPDATE OR DELETE
+    ON api_keys
+    FOR EACH ROW
+EXECUTE PROCEDURE notify_api_keys_changes();
@@ -0,0 +1,22 @@
+ALTER TABLE users
+    ADD COLUMN role TEXT NOT NULL DEFAULT 'user';
+
+CREATE OR REPLACE FUNCTION notify_users_roles() RETURNS TRIGGER AS
+$$
+BEGIN
+    PERFORM pg_notify('sessions_changes', json_build_object(
+            'op', 'role',
+            'user_id', NEW.id,
+            'role', NEW.role
+        )::text);
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER users_roles_changes
+    AFTER UPDATE OF role
+    ON users
+    FOR EACH ROW
+    WHEN (OLD.role IS DISTINCT FROM NEW.role)
+EXECUTE PROCEDURE notify_users_roles();
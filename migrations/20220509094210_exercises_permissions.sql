@@ -0,0 +1,6 @@
+ALTER TABLE exercises
+    ADD COLUMN permissions JSONB NOT NULL DEFAULT '{}';
+
+-- exercises used to be readable by everyone, existing ones stay public
+UPDATE exercises
+SET permissions = '{"0": 1}';
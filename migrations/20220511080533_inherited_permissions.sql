@@ -0,0 +1,9 @@
+UPDATE workout_sets
+SET permissions = workouts.permissions
+FROM workouts
+WHERE workout_sets.workout_id = workouts.id;
+
+UPDATE workout_repeats
+SET permissions = workout_sets.permissions
+FROM workout_sets
+WHERE workout_repeats.workout_set_id = workout_sets.id;
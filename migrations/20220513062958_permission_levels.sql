@@ -0,0 +1,20 @@
+-- owners move from level 2 to 3, 2 now stands for editors
+UPDATE workouts
+SET permissions = (SELECT jsonb_object_agg(key, CASE WHEN value::integer > 1 THEN 3 ELSE value::integer END)
+                   FROM jsonb_each_text(permissions))
+WHERE permissions <> '{}';
+
+UPDATE workout_sets
+SET permissions = (SELECT jsonb_object_agg(key, CASE WHEN value::integer > 1 THEN 3 ELSE value::integer END)
+                   FROM jsonb_each_text(permissions))
+WHERE permissions <> '{}';
+
+UPDATE workout_repeats
+SET permissions = (SELECT jsonb_object_agg(key, CASE WHEN value::integer > 1 THEN 3 ELSE value::integer END)
+                   FROM jsonb_each_text(permissions))
+WHERE permissions <> '{}';
+
+UPDATE exercises
+SET permissions = (SELECT jsonb_object_agg(key, CASE WHEN value::integer > 1 THEN 3 ELSE value::integer END)
+                   FROM jsonb_each_text(permissions))
+WHERE permissions <> '{}';
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS workout_share_links
+(
+    id         SERIAL PRIMARY KEY,
+    created_at timestamptz NOT NULL DEFAULT NOW()::timestamp,
+    workout_id INT         NOT NULL REFERENCES workouts (id) ON DELETE CASCADE,
+    user_id    INT         NOT NULL,
+    slug       TEXT        NOT NULL UNIQUE
+);
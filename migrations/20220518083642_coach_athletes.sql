@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS coach_athletes
+(
+    id          SERIAL PRIMARY KEY,
+    created_at  timestamptz NOT NULL DEFAULT NOW()::timestamp,
+    updated_at  timestamptz NOT NULL DEFAULT NOW()::timestamp,
+    coach_id    INT         NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    athlete_id  INT         NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    accepted_at timestamptz,
+    UNIQUE (coach_id, athlete_id),
+    CHECK (coach_id <> athlete_id)
+);
+
+CREATE INDEX IF NOT EXISTS coach_athletes_athlete_id_idx ON coach_athletes (athlete_id);
@@ -0,0 +1,20 @@
+CREATE TABLE IF NOT EXISTS groups
+(
+    id         SERIAL PRIMARY KEY,
+    created_at timestamptz NOT NULL DEFAULT NOW()::timestamp,
+    updated_at timestamptz NOT NULL DEFAULT NOW()::timestamp,
+    name       TEXT        NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS group_members
+(
+    id         SERIAL PRIMARY KEY,
+    created_at timestamptz NOT NULL DEFAULT NOW()::timestamp,
+    updated_at timestamptz NOT NULL DEFAULT NOW()::timestamp,
+    group_id   INT         NOT NULL REFERENCES groups (id) ON DELETE CASCADE,
+    user_id    INT         NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    role       TEXT        NOT NULL DEFAULT 'member',
+    UNIQUE (group_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS group_members_user_id_idx ON group_members (user_id);
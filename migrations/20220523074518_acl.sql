@@ -0,0 +1,68 @@
+-- `permissions` stays the written copy, the acl table is kept in sync by triggers and serves reads
+CREATE TABLE IF NOT EXISTS acl
+(
+    resource_type TEXT NOT NULL,
+    resource_id   INT  NOT NULL,
+    principal     TEXT NOT NULL,
+    level         INT  NOT NULL,
+    PRIMARY KEY (resource_type, resource_id, principal)
+);
+
+CREATE INDEX IF NOT EXISTS acl_principal_idx ON acl (principal, resource_type, level, resource_id);
+
+CREATE OR REPLACE FUNCTION sync_acl() RETURNS TRIGGER AS
+$$
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+        DELETE FROM acl WHERE resource_type = TG_TABLE_NAME AND resource_id = OLD.id;
+    END IF;
+
+    IF TG_OP IN ('INSERT', 'UPDATE') THEN
+        INSERT INTO acl (resource_type, resource_id, principal, level)
+        SELECT TG_TABLE_NAME, NEW.id, key, value::integer
+        FROM jsonb_each_text(NEW.permissions);
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER workouts_acl
+    AFTER INSERT OR UPDATE OF permissions OR DELETE
+    ON workouts
+    FOR EACH ROW
+EXECUTE PROCEDURE sync_acl();
+
+CREATE TRIGGER workout_sets_acl
+    AFTER INSERT OR UPDATE OF permissions OR DELETE
+    ON workout_sets
+    FOR EACH ROW
+EXECUTE PROCEDURE sync_acl();
+
+CREATE TRIGGER workout_repeats_acl
+    AFTER INSERT OR UPDATE OF permissions OR DELETE
+    ON workout_repeats
+    FOR EACH ROW
+EXECUTE PROCEDURE sync_acl();
+
+CREATE TRIGGER exercises_acl
+    AFTER INSERT OR UPDATE OF permissions OR DELETE
+    ON exercises
+    FOR EACH ROW
+EXECUTE PROCEDURE sync_acl();
+
+INSERT INTO acl (resource_type, resource_id, principal, level)
+SELECT 'workouts', id, key, value::integer
+FROM workouts, jsonb_each_text(permissions);
+
+INSERT INTO acl (resource_type, resource_id, principal, level)
+SELECT 'workout_sets', id, key, value::integer
+FROM workout_sets, jsonb_each_text(permissions);
+
+INSERT INTO acl (resource_type, resource_id, principal, level)
+SELECT 'workout_repeats', id, key, value::integer
+FROM workout_repeats, jsonb_each_text(permissions);
+
+INSERT INTO acl (resource_type, resource_id, principal, level)
+SELECT 'exercises', id, key, value::integer
+FROM exercises, jsonb_each_text(permissions);
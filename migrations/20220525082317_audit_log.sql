@@ -0,0 +1,28 @@
+CREATE TABLE IF NOT EXISTS audit_log
+(
+    id            BIGSERIAL PRIMARY KEY,
+    created_at    timestamptz NOT NULL DEFAULT NOW()::timestamp,
+    actor_id      INT         NOT NULL,
+    rpc           TEXT        NOT NULL,
+    resource_type TEXT        NOT NULL,
+    resource_id   INT         NOT NULL,
+    before        JSONB,
+    after         JSONB
+);
+
+CREATE INDEX IF NOT EXISTS audit_log_resource_idx ON audit_log (resource_type, resource_id, id);
+CREATE INDEX IF NOT EXISTS audit_log_actor_id_idx ON audit_log (actor_id, id);
+
+-- append-only, entries can't be changed once written
+CREATE OR REPLACE FUNCTION reject_audit_log_changes() RETURNS TRIGGER AS
+$$
+BEGIN
+    RAISE EXCEPTION 'audit_log is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER audit_log_append_only
+    BEFORE UPDATE OR DELETE
+    ON audit_log
+    FOR EACH ROW
+EXECUTE PROCEDURE reject_audit_log_changes();
@@ -0,0 +1,36 @@
+ALTER TABLE users
+    ADD COLUMN disabled_at timestamptz;
+
+-- api keys of disabled users leave the interceptor cache until the account is enabled again
+CREATE OR REPLACE FUNCTION notify_users_disabled() RETURNS TRIGGER AS
+$$
+DECLARE
+    api_key RECORD;
+BEGIN
+    FOR api_key IN SELECT key_hash, user_id, scopes FROM api_keys WHERE user_id = NEW.id
+        LOOP
+            IF NEW.disabled_at IS NOT NULL THEN
+                PERFORM pg_notify('sessions_changes', json_build_object(
+                        'op', 'api_key_delete',
+                        'key_hash', api_key.key_hash
+                    )::text);
+            ELSE
+                PERFORM pg_notify('sessions_changes', json_build_object(
+                        'op', 'api_key_insert',
+                        'key_hash', api_key.key_hash,
+                        'user_id', api_key.user_id,
+                        'scopes', api_key.scopes
+                    )::text);
+            END IF;
+        END LOOP;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER users_disabled_changes
+    AFTER UPDATE OF disabled_at
+    ON users
+    FOR EACH ROW
+    WHEN ((OLD.disabled_at IS NULL) IS DISTINCT FROM (NEW.disabled_at IS NULL))
+EXECUTE PROCEDURE notify_users_disabled();
@@ -0,0 +1,29 @@
+ALTER TABLE sessions
+    ADD COLUMN impersonator_id INT REFERENCES users (id) ON DELETE CASCADE;
+
+ALTER TABLE audit_log
+    ADD COLUMN impersonator_id INT;
+
+CREATE OR REPLACE FUNCTION notify_sessions_changes() RETURNS TRIGGER AS
+$$
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+        PERFORM pg_notify('sessions_changes', json_build_object(
+                'op', 'delete',
+                'token', OLD.token
+            )::text);
+    END IF;
+
+    IF TG_OP IN ('INSERT', 'UPDATE') THEN
+        PERFORM pg_notify('sessions_changes', json_build_object(
+                'op', 'insert',
+                'token', NEW.token,
+                'user_id', NEW.user_id,
+                'impersonator_id', NEW.impersonator_id,
+                'expires_at', EXTRACT(EPOCH FROM NEW.expires_at)::BIGINT
+            )::text);
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
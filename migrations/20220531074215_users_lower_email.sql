@@ -0,0 +1,24 @@
+-- accounts differing only by case can't be merged safely here, they have to be resolved by hand
+DO
+$$
+DECLARE
+    duplicates TEXT;
+BEGIN
+    SELECT string_agg(email, ', ')
+    INTO duplicates
+    FROM (SELECT lower(trim(email)) AS email FROM users GROUP BY 1 HAVING COUNT(*) > 1) AS emails;
+
+    IF duplicates IS NOT NULL THEN
+        RAISE EXCEPTION 'users share these emails once lowercased: %', duplicates;
+    END IF;
+END
+$$;
+
+UPDATE users
+SET email = lower(trim(email))
+WHERE email <> lower(trim(email));
+
+ALTER TABLE users
+    DROP CONSTRAINT users_email_key;
+
+CREATE UNIQUE INDEX users_email_key ON users (lower(email));
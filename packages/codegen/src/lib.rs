@@ -30,6 +30,27 @@ pub struct CodegenPackage {
     pub create: Option<&'static str>,
     pub update: Option<&'static str>,
     pub delete: Option<&'static str>,
+    pub share: Option<&'static str>,
+    pub unshare: Option<&'static str>,
+    pub collaborators: Option<&'static str>,
+    /// Returns the object with its children, served by `get_<message>_tree` of the
+    /// `<message>_tree` module.
+    pub tree: Option<&'static str>,
+    /// Optional create request field naming the athlete a coach creates the object for.
+    pub owner: Option<&'static str>,
+    /// Columns list requests may sort on.
+    pub sortable: &'static [(&'static str, FieldKind)],
+    /// Columns list requests may filter on.
+    pub filterable: &'static [(&'static str, FieldKind)],
+}
+
+/// Mirrors `queryable::FieldKind` of the server.
+#[derive(Copy, Clone, Debug)]
+pub enum FieldKind {
+    Int,
+    Float,
+    Text,
+    Timestamp,
 }
 
 #[derive(Default)]
@@ -74,8 +95,16 @@ impl Codegen {
 
                 use crate::proto::proto;
                 use crate::Queryable;
+                use crate::queryable::FieldKind;
                 use crate::query_builder::QueryBuilder;
                 use crate::me_extension::MeExtension;
+                use crate::pagination;
+                use crate::sharing;
+                use crate::sorting;
+                use crate::coaching;
+                use crate::errors;
+                use crate::filtering;
+                use crate::audit;
             }
         ));
 
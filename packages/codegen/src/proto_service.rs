@@ -1,8 +1,12 @@
+mod collaborators;
 mod create;
 mod delete;
 mod get;
 mod list;
 pub mod messages;
+mod share;
+mod tree;
+mod unshare;
 mod update;
 
 use std::collections::HashMap;
@@ -11,10 +15,14 @@ use prost_types::{DescriptorProto, ServiceDescriptorProto};
 use quote::__private::TokenStream;
 
 use crate::naive_snake_case::naive_snake_case;
+use crate::proto_service::collaborators::proto_service_collaborators;
 use crate::proto_service::create::proto_service_create;
 use crate::proto_service::delete::proto_service_delete;
 use crate::proto_service::get::proto_service_get;
 use crate::proto_service::list::proto_service_list;
+use crate::proto_service::share::proto_service_share;
+use crate::proto_service::tree::proto_service_tree;
+use crate::proto_service::unshare::proto_service_unshare;
 use crate::proto_service::update::proto_service_update;
 use crate::CodegenPackage;
 
@@ -34,13 +42,15 @@ pub fn proto_service(
     let create_tokens = proto_service_create(service, messages, package);
     let update_tokens = proto_service_update(service, messages, package);
     let delete_tokens = proto_service_delete(service, messages, package);
+    let share_tokens = proto_service_share(service, messages, package);
+    let unshare_tokens = proto_service_unshare(service, messages, package);
+    let collaborators_tokens = proto_service_collaborators(service, messages, package);
+    let tree_tokens = proto_service_tree(service, messages, package);
 
     quote::quote! {
         #[tonic::async_trait]
         impl proto::santa_cruz::#service_server::#service_name for #service_name
         {
-            #list_tokens
-
             #get_tokens
 
             #create_tokens
@@ -48,6 +58,16 @@ pub fn proto_service(
             #update_tokens
 
             #delete_tokens
+
+            #list_tokens
+
+            #share_tokens
+
+            #unshare_tokens
+
+            #collaborators_tokens
+
+            #tree_tokens
         }
     }
 }
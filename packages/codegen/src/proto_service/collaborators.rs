@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+
+use prost_types::{DescriptorProto, MethodDescriptorProto, ServiceDescriptorProto};
+use quote::__private::TokenStream;
+
+use crate::proto_request_name::proto_request_name;
+use crate::proto_request_params::proto_request_params;
+use crate::CodegenPackage;
+
+pub fn proto_service_collaborators(
+    service: &ServiceDescriptorProto,
+    messages: &HashMap<&str, DescriptorProto>,
+    package: &CodegenPackage,
+) -> TokenStream {
+    if package.collaborators.is_none() {
+        return quote::quote! {};
+    }
+
+    let action = &service
+        .method
+        .clone()
+        .into_iter()
+        .find(|m| m.name() == package.collaborators.unwrap());
+
+    if let Some(action) = action {
+        let MethodDescriptorProto { output_type, .. } = action.clone();
+
+        let output_type = output_type.unwrap();
+
+        let res = messages
+            .get(output_type.split(".").collect::<Vec<_>>().last().unwrap())
+            .expect("output not found");
+
+        let res_name = quote::format_ident!("{}", res.name());
+        let table = package.table;
+
+        let proto_service_name = proto_request_name(action, messages);
+        let proto_service_params = proto_request_params(action, messages);
+
+        return quote::quote! {
+            async fn #proto_service_name {
+                let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+                #proto_service_params
+
+                let collaborators = sharing::list_collaborators(&self.pool, #table, *id, *user_id).await?;
+
+                Ok(Response::new(#res_name { collaborators }))
+            }
+        };
+    }
+
+    return quote::quote! {};
+}
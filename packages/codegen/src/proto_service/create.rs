@@ -33,22 +33,41 @@ pub fn proto_service_create(
         let proto_service_params = proto_request_params(action, messages);
         let message_name = quote::format_ident!("{}", message.name());
 
+        let table = package.table;
+
         let return_by_id =
             quote::format_ident!("return_{}_by_id", naive_snake_case(message.name()));
 
         let proto_service_create_restricted_fields =
-            proto_service_create_restricted_fields(action, messages);
+            proto_service_create_restricted_fields(action, messages, package.owner);
+
+        let owner_id = match package.owner {
+            Some(owner) => {
+                let owner = quote::format_ident!("{}", owner);
+
+                quote::quote! {
+                    let owner_id = coaching::resolve_owner(&self.pool, *user_id, *#owner)
+                        .await
+                        .map_err(Status::permission_denied)?;
+                }
+            }
+            None => quote::quote! {
+                let owner_id = *user_id;
+            },
+        };
 
         return quote::quote! {
             async fn #proto_service_name {
-                let MeExtension { user_id } = request.extensions().get::<MeExtension>().unwrap();
+                let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
 
                 #proto_service_params
 
                 let mut query_builder = #message_name::query();
 
+                #owner_id
+
                 let mut permissions = HashMap::new();
-                permissions.insert(user_id, 2);
+                permissions.insert(owner_id, sharing::Level::Owner as i32);
                 query_builder.field_with_argument("permissions", Json(permissions));
 
                 #( #proto_service_create_restricted_fields )*
@@ -60,7 +79,11 @@ pub fn proto_service_create(
                     .await
                     .expect("create error");
 
-                self.#return_by_id(rec.get::<i32, _>("id"), *user_id).await
+                let id = rec.get::<i32, _>("id");
+
+                audit::record(&self.pool, request.extensions(), #table, id, None).await;
+
+                self.#return_by_id(id, *user_id).await
             }
         };
     }
@@ -6,6 +6,7 @@ use quote::__private::TokenStream;
 pub fn proto_service_create_restricted_fields(
     action: &MethodDescriptorProto,
     messages: &HashMap<&str, DescriptorProto>,
+    owner: Option<&str>,
 ) -> Vec<TokenStream> {
     let MethodDescriptorProto { input_type, .. } = action.clone();
 
@@ -21,6 +22,11 @@ pub fn proto_service_create_restricted_fields(
         let FieldDescriptorProto { name, .. } = field.clone();
 
         let field_key = name.unwrap();
+
+        if owner == Some(field_key.as_str()) {
+            continue;
+        }
+
         let field_value = quote::format_ident!("{}", field_key);
 
         result.push(quote::quote! {
@@ -39,23 +39,32 @@ pub fn proto_service_delete(
         let proto_service_params = proto_request_params(action, messages);
 
         let message_name = quote::format_ident!("{}", message.name());
+        let table = package.table;
 
         return quote::quote! {
             async fn #proto_service_name {
-                let MeExtension { user_id } = &request.extensions().get::<MeExtension>().unwrap();
+                let MeExtension { user_id, .. } = &request.extensions().get::<MeExtension>().unwrap();
                 #proto_service_params
 
                 let mut query_builder = #message_name::query();
-                query_builder.where_raw("(permissions ->> CAST(${index} as text))::integer > 1", user_id);
+                query_builder.where_permission(sharing::Level::Owner, *user_id);
                 query_builder.where_eq("id", id);
 
                 let sql = query_builder.delete_query();
 
-                sqlx::query_with(sql.0.as_str(), sql.1)
+                let before = audit::snapshot(&self.pool, #table, *id).await;
+
+                let result = sqlx::query_with(sql.0.as_str(), sql.1)
                     .execute(&self.pool)
                     .await
                     .expect("delete error");
 
+                if result.rows_affected() == 0 {
+                    return Err(sharing::write_denied(&self.pool, #table, *id, *user_id, sharing::Level::Owner).await);
+                }
+
+                audit::record(&self.pool, request.extensions(), #table, *id, before).await;
+
                 Ok(Response::new(#res_name {}))
             }
         };
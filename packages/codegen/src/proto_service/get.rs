@@ -34,7 +34,7 @@ pub fn proto_service_get(
 
         return quote::quote! {
             async fn #proto_service_name {
-                let MeExtension { user_id } = request.extensions().get::<MeExtension>().unwrap();
+                let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
                 #proto_service_params
 
                 self.#return_by_id(*id, *user_id).await
@@ -44,23 +44,33 @@ pub fn proto_service_list(
 
         return quote::quote! {
             async fn #proto_service_name {
-                let MeExtension { user_id } = request.extensions().get::<MeExtension>().unwrap();
+                let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
                 #proto_service_params
 
+                let page_size = pagination::page_size(*page_size);
+                let cursor = pagination::Cursor::decode(page_token).map_err(Status::invalid_argument)?;
+
+                let order = sorting::parse(sort, &#message_name::sortable()).map_err(Status::invalid_argument)?;
+
                 let mut query_builder = #message_name::query();
-                query_builder.where_raw("((permissions ->> CAST(${index} as text))::integer > 0 OR (permissions ->> '0')::integer > 0)", user_id);
+                query_builder.where_permission(sharing::Level::Viewer, *user_id);
+                filtering::apply(&mut query_builder, filter.as_ref(), &#message_name::filterable())
+                    .map_err(Status::invalid_argument)?;
+                query_builder.order_by(order);
+                query_builder.paginate(page_size, cursor.as_ref(), &#message_name::sortable())
+                    .map_err(Status::invalid_argument)?;
 
                 let sql = query_builder.select_query();
 
-                let #res_field_name = sqlx::query_with(sql.0.as_str(), sql.1)
+                let mut rows = sqlx::query_with(sql.0.as_str(), sql.1)
                     .fetch_all(&self.pool)
                     .await
-                    .expect("error")
-                    .into_iter()
-                    .map(|row| row.into())
-                    .collect();
+                    .map_err(errors::internal)?;
+
+                let next_page_token = pagination::next_page_token(&mut rows, page_size, pagination::row_cursor);
+                let #res_field_name = rows.into_iter().map(|row| row.into()).collect();
 
-                Ok(Response::new(#res_name { #res_field_name }))
+                Ok(Response::new(#res_name { #res_field_name, next_page_token }))
             }
         };
     }
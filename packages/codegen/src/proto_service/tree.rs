@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+
+use prost_types::{DescriptorProto, ServiceDescriptorProto};
+use quote::__private::TokenStream;
+
+use crate::naive_snake_case::naive_snake_case;
+use crate::proto_request_name::proto_request_name;
+use crate::proto_request_params::proto_request_params;
+use crate::CodegenPackage;
+
+pub fn proto_service_tree(
+    service: &ServiceDescriptorProto,
+    messages: &HashMap<&str, DescriptorProto>,
+    package: &CodegenPackage,
+) -> TokenStream {
+    if package.tree.is_none() {
+        return quote::quote! {};
+    }
+
+    let message = messages.get(&package.message).unwrap();
+
+    let action = &service
+        .method
+        .clone()
+        .into_iter()
+        .find(|m| m.name() == package.tree.unwrap());
+
+    if let Some(action) = action {
+        let proto_service_name = proto_request_name(action, messages);
+        let proto_service_params = proto_request_params(action, messages);
+
+        let snake = naive_snake_case(message.name());
+        let tree_module = quote::format_ident!("{}_tree", snake);
+        let get_tree = quote::format_ident!("get_{}_tree", snake);
+
+        return quote::quote! {
+            async fn #proto_service_name {
+                let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+                #proto_service_params
+
+                crate::#tree_module::#get_tree(&self.pool, *id, *user_id)
+                    .await
+                    .map(Response::new)
+            }
+        };
+    }
+
+    return quote::quote! {};
+}
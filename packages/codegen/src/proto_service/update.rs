@@ -41,10 +41,11 @@ pub fn proto_service_update(
         let get_by_id = quote::format_ident!("get_{}_by_id", naive_snake_case(message.name()));
 
         let optional_fields = proto_service_update_optional_fields(action, messages);
+        let table = package.table;
 
         return quote::quote! {
             async fn #proto_service_name {
-                let MeExtension { user_id } = request.extensions().get::<MeExtension>().unwrap();
+                let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
 
                 #proto_service_params
 
@@ -57,6 +58,13 @@ pub fn proto_service_update(
                     )));
                 }
 
+                if !sharing::has_level(&self.pool, #table, *id, *user_id, sharing::Level::Editor).await {
+                    return Err(Status::permission_denied(format!(
+                        "editor permission required for object #{}",
+                        id
+                    )));
+                }
+
                 let mut query_builder = #message_name::query();
 
                 #( #optional_fields )*
@@ -65,6 +73,8 @@ pub fn proto_service_update(
                     return Ok(Response::new(original.unwrap()));
                 }
 
+                let before = audit::snapshot(&self.pool, #table, *id).await;
+
                 query_builder.field_with_argument("updated_at", Utc::now());
 
                 query_builder.where_eq("id", id);
@@ -76,6 +86,8 @@ pub fn proto_service_update(
                     .await
                     .expect("update_workout_repeat error");
 
+                audit::record(&self.pool, request.extensions(), #table, *id, before).await;
+
                 self.#return_by_id(*id, *user_id).await
             }
         };
@@ -14,12 +14,39 @@ pub fn queryable(message: &DescriptorProto, package: &CodegenPackage) -> TokenSt
         .map(|field| format!("{}", field.name()))
         .collect::<Vec<_>>();
 
+    let sortable_fields = package
+        .sortable
+        .iter()
+        .map(|(field, kind)| {
+            let kind = quote::format_ident!("{}", format!("{:?}", kind));
+
+            quote::quote! { (#field, FieldKind::#kind) }
+        })
+        .collect::<Vec<_>>();
+    let filterable_fields = package
+        .filterable
+        .iter()
+        .map(|(field, kind)| {
+            let kind = quote::format_ident!("{}", format!("{:?}", kind));
+
+            quote::quote! { (#field, FieldKind::#kind) }
+        })
+        .collect::<Vec<_>>();
+
     quote::quote! {
         impl Queryable for #message_name {
             fn fields() -> Vec<&'static str> {
                vec![ #(#queryable_fields ,)* ]
             }
 
+            fn sortable() -> Vec<(&'static str, FieldKind)> {
+                vec![ #(#sortable_fields ,)* ]
+            }
+
+            fn filterable() -> Vec<(&'static str, FieldKind)> {
+                vec![ #(#filterable_fields ,)* ]
+            }
+
             fn table() -> &'static str {
                 #table_name
             }
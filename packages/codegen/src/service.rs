@@ -26,7 +26,7 @@ pub fn service(message: &DescriptorProto, _package: &CodegenPackage) -> TokenStr
                 user_id: i32,
             ) -> Option<#message_name> {
                 let mut query_builder = #message_name::query();
-                query_builder.where_raw("((permissions ->> CAST(${index} as text))::integer > 0 OR (permissions ->> '0')::integer > 0)", user_id);
+                query_builder.where_permission(sharing::Level::Viewer, user_id);
                 query_builder.where_eq("id", id);
 
                 let sql = query_builder.select_query();
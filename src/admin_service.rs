@@ -0,0 +1,408 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use serde_json::json;
+use sqlx::PgPool;
+use tonic::{Request, Response, Status};
+
+use crate::audit;
+use crate::auth_service::{access_token, generate_token};
+use crate::filtering;
+use crate::jwt::JwtKeys;
+use crate::me_extension::{MeExtension, Role};
+use crate::proto::proto::santa_cruz;
+use crate::proto::proto::santa_cruz::{
+    AdminUser, DisableUserRequest, EnableUserRequest, GetUserResourceCountsRequest,
+    GetUsersRequest, GetUsersResponse, ImpersonateUserRequest, ImpersonateUserResponse,
+    RevokeUserSessionsRequest, RevokeUserSessionsResponse, UserResourceCounts,
+};
+use crate::session_service::revoke_user_sessions;
+use crate::sessions_cache::CachedSession;
+use crate::sharing::Level;
+use crate::SessionsCache;
+
+const DEFAULT_PAGE_SIZE: i32 = 50;
+const MAX_PAGE_SIZE: i32 = 200;
+const IMPERSONATION_TTL_MINUTES: i64 = 15;
+const REASON_MAX_LENGTH: usize = 200;
+
+const USER_SELECT: &str =
+    r#"SELECT id, created_at, updated_at, email, role, totp_enabled, disabled_at FROM users"#;
+
+pub struct AdminService {
+    pool: PgPool,
+    cache: Arc<SessionsCache>,
+    jwt: Option<Arc<JwtKeys>>,
+}
+
+type UserRow = (
+    i32,
+    DateTime<Utc>,
+    DateTime<Utc>,
+    String,
+    String,
+    bool,
+    Option<DateTime<Utc>>,
+);
+
+impl From<UserRow> for AdminUser {
+    fn from(row: UserRow) -> Self {
+        AdminUser {
+            id: row.0,
+            created_at: row.1.to_rfc3339(),
+            updated_at: row.2.to_rfc3339(),
+            email: row.3,
+            role: row.4,
+            two_factor_enabled: row.5,
+            disabled: row.6.is_some(),
+            disabled_at: row.6.map(|at| at.to_rfc3339()).unwrap_or_default(),
+        }
+    }
+}
+
+fn like_pattern(query: &str) -> String {
+    format!("%{}%", filtering::escape_like(&query.trim().to_lowercase()))
+}
+
+impl AdminService {
+    pub fn new(
+        pool: &PgPool,
+        cache: Arc<SessionsCache>,
+        jwt: Option<Arc<JwtKeys>>,
+    ) -> AdminService {
+        AdminService {
+            pool: pool.clone(),
+            cache,
+            jwt,
+        }
+    }
+
+    async fn get_user_by_id(&self, user_id: i32) -> Option<AdminUser> {
+        let row: Option<UserRow> = sqlx::query_as(&format!("{} WHERE id = $1", USER_SELECT))
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await
+            .expect("get_user_by_id error");
+
+        row.map(|row| row.into())
+    }
+
+    async fn count(&self, query: &str, user_id: i32) -> i64 {
+        let (count,): (i64,) = sqlx::query_as(query)
+            .bind(user_id)
+            .fetch_one(&self.pool)
+            .await
+            .expect("count error");
+
+        count
+    }
+}
+
+#[tonic::async_trait]
+impl santa_cruz::admin_service_server::AdminService for AdminService {
+    async fn get_users(
+        &self,
+        request: Request<GetUsersRequest>,
+    ) -> Result<Response<GetUsersResponse>, Status> {
+        let GetUsersRequest {
+            query,
+            page_size,
+            page_token,
+        } = request.get_ref();
+
+        let page_size = match *page_size {
+            size if size <= 0 => DEFAULT_PAGE_SIZE,
+            size => size.min(MAX_PAGE_SIZE),
+        };
+
+        let after_id = match page_token.as_str() {
+            "" => 0,
+            token => token
+                .parse::<i32>()
+                .map_err(|_| Status::invalid_argument("invalid page token"))?,
+        };
+
+        let rows: Vec<UserRow> = sqlx::query_as(&format!(
+            "{} WHERE id > $1 AND lower(email) LIKE $2 ORDER BY id LIMIT $3",
+            USER_SELECT
+        ))
+        .bind(after_id)
+        .bind(like_pattern(query))
+        .bind(i64::from(page_size) + 1)
+        .fetch_all(&self.pool)
+        .await
+        .expect("get_users error");
+
+        let mut users: Vec<AdminUser> = rows.into_iter().map(|row| row.into()).collect();
+
+        let next_page_token = match users.len() > page_size as usize {
+            true => {
+                users.truncate(page_size as usize);
+                users
+                    .last()
+                    .map(|user| user.id.to_string())
+                    .unwrap_or_default()
+            }
+            false => String::new(),
+        };
+
+        Ok(Response::new(GetUsersResponse {
+            users,
+            next_page_token,
+        }))
+    }
+
+    /// Disabled users can't log in and lose their sessions, their api keys stop working until
+    /// the account is enabled again.
+    async fn disable_user(
+        &self,
+        request: Request<DisableUserRequest>,
+    ) -> Result<Response<AdminUser>, Status> {
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+        let DisableUserRequest { user_id: target_id } = request.get_ref();
+
+        if target_id == user_id {
+            return Err(Status::invalid_argument(
+                "you can't disable your own account",
+            ));
+        }
+
+        let before = audit::snapshot(&self.pool, "users", *target_id).await;
+
+        let result = sqlx::query(
+            r#"UPDATE users SET disabled_at = NOW(), updated_at = NOW() WHERE id = $1 AND disabled_at IS NULL"#,
+        )
+        .bind(target_id)
+        .execute(&self.pool)
+        .await
+        .expect("disable_user error");
+
+        if result.rows_affected() > 0 {
+            sqlx::query(r#"DELETE FROM login_challenges WHERE user_id = $1"#)
+                .bind(target_id)
+                .execute(&self.pool)
+                .await
+                .expect("disable_user error");
+
+            revoke_user_sessions(&self.pool, &self.cache, *target_id, None).await;
+
+            audit::record(
+                &self.pool,
+                request.extensions(),
+                "users",
+                *target_id,
+                before,
+            )
+            .await;
+        }
+
+        self.get_user_by_id(*target_id)
+            .await
+            .map(Response::new)
+            .ok_or_else(|| Status::not_found(format!("user #{} not found", target_id)))
+    }
+
+    async fn enable_user(
+        &self,
+        request: Request<EnableUserRequest>,
+    ) -> Result<Response<AdminUser>, Status> {
+        let EnableUserRequest { user_id } = request.get_ref();
+
+        let before = audit::snapshot(&self.pool, "users", *user_id).await;
+
+        let result = sqlx::query(
+            r#"UPDATE users SET disabled_at = NULL, updated_at = NOW() WHERE id = $1 AND disabled_at IS NOT NULL"#,
+        )
+        .bind(user_id)
+        .execute(&self.pool)
+        .await
+        .expect("enable_user error");
+
+        if result.rows_affected() > 0 {
+            audit::record(&self.pool, request.extensions(), "users", *user_id, before).await;
+        }
+
+        self.get_user_by_id(*user_id)
+            .await
+            .map(Response::new)
+            .ok_or_else(|| Status::not_found(format!("user #{} not found", user_id)))
+    }
+
+    async fn revoke_user_sessions(
+        &self,
+        request: Request<RevokeUserSessionsRequest>,
+    ) -> Result<Response<RevokeUserSessionsResponse>, Status> {
+        let RevokeUserSessionsRequest { user_id } = request.get_ref();
+
+        if self.get_user_by_id(*user_id).await.is_none() {
+            return Err(Status::not_found(format!("user #{} not found", user_id)));
+        }
+
+        let revoked = revoke_user_sessions(&self.pool, &self.cache, *user_id, None).await;
+
+        if revoked > 0 {
+            let user = audit::snapshot(&self.pool, "users", *user_id).await;
+            let after = user.clone().map(|mut user| {
+                user["revoked_sessions"] = json!(revoked);
+                user
+            });
+
+            audit::record_with(
+                &self.pool,
+                request.extensions(),
+                "users",
+                *user_id,
+                user,
+                after,
+            )
+            .await;
+        }
+
+        Ok(Response::new(RevokeUserSessionsResponse {
+            revoked: revoked as i32,
+        }))
+    }
+
+    async fn get_user_resource_counts(
+        &self,
+        request: Request<GetUserResourceCountsRequest>,
+    ) -> Result<Response<UserResourceCounts>, Status> {
+        let GetUserResourceCountsRequest { user_id } = request.get_ref();
+
+        if self.get_user_by_id(*user_id).await.is_none() {
+            return Err(Status::not_found(format!("user #{} not found", user_id)));
+        }
+
+        let owned: Vec<(String, i64)> = sqlx::query_as(
+            r#"SELECT resource_type, COUNT(*) FROM acl WHERE principal = CAST($1 as text) AND level >= $2 GROUP BY resource_type"#,
+        )
+        .bind(user_id)
+        .bind(Level::Owner as i32)
+        .fetch_all(&self.pool)
+        .await
+        .expect("get_user_resource_counts error");
+
+        let owned_count = |resource_type: &str| {
+            owned
+                .iter()
+                .find(|(owned_type, _)| owned_type == resource_type)
+                .map(|(_, count)| *count)
+                .unwrap_or_default()
+        };
+
+        Ok(Response::new(UserResourceCounts {
+            user_id: *user_id,
+            workouts: owned_count("workouts"),
+            workout_sets: owned_count("workout_sets"),
+            workout_repeats: owned_count("workout_repeats"),
+            exercises: owned_count("exercises"),
+            sessions: self
+                .count(
+                    r#"SELECT COUNT(*) FROM sessions WHERE user_id = $1"#,
+                    *user_id,
+                )
+                .await,
+            api_keys: self
+                .count(
+                    r#"SELECT COUNT(*) FROM api_keys WHERE user_id = $1"#,
+                    *user_id,
+                )
+                .await,
+            groups: self
+                .count(
+                    r#"SELECT COUNT(*) FROM group_members WHERE user_id = $1"#,
+                    *user_id,
+                )
+                .await,
+        }))
+    }
+
+    /// Issues a short-lived session acting as the user. It can't be refreshed, can't touch the
+    /// user's credentials and shows up in their session list, changes made through it are
+    /// audited with the admin as impersonator.
+    async fn impersonate_user(
+        &self,
+        request: Request<ImpersonateUserRequest>,
+    ) -> Result<Response<ImpersonateUserResponse>, Status> {
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+        let ImpersonateUserRequest {
+            user_id: target_id,
+            reason,
+        } = request.get_ref();
+
+        let reason = reason.trim();
+
+        if reason.is_empty() || reason.len() > REASON_MAX_LENGTH {
+            return Err(Status::invalid_argument(format!(
+                "reason must be between 1 and {} characters",
+                REASON_MAX_LENGTH
+            )));
+        }
+
+        if target_id == user_id {
+            return Err(Status::invalid_argument("you can't impersonate yourself"));
+        }
+
+        let user = self
+            .get_user_by_id(*target_id)
+            .await
+            .ok_or_else(|| Status::not_found(format!("user #{} not found", target_id)))?;
+
+        if user.disabled {
+            return Err(Status::failed_precondition(format!(
+                "user #{} is disabled",
+                target_id
+            )));
+        }
+
+        if Role::parse(&user.role) == Some(Role::Admin) {
+            return Err(Status::permission_denied("admins can't be impersonated"));
+        }
+
+        let token = generate_token();
+        let expires_at = Utc::now() + Duration::minutes(IMPERSONATION_TTL_MINUTES);
+
+        // no refresh token, the session is gone from the list once it expires
+        let (session_id,): (i32,) = sqlx::query_as(
+            r#"INSERT INTO sessions ( user_id, token, device_name, expires_at, refresh_expires_at, impersonator_id ) VALUES ( $1, $2, $3, $4, $4, $5 ) RETURNING id"#,
+        )
+        .bind(target_id)
+        .bind(&token)
+        .bind(format!("Impersonated by admin #{}: {}", user_id, reason))
+        .bind(expires_at)
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await
+        .expect("impersonate_user error");
+
+        self.cache.insert(
+            token.clone(),
+            CachedSession {
+                user_id: *target_id,
+                impersonator_id: Some(*user_id),
+                expires_at,
+            },
+        );
+
+        audit::record(
+            &self.pool,
+            request.extensions(),
+            "sessions",
+            session_id,
+            None,
+        )
+        .await;
+
+        Ok(Response::new(ImpersonateUserResponse {
+            access_token: access_token(
+                self.jwt.as_deref(),
+                *target_id,
+                Some(*user_id),
+                &token,
+                expires_at,
+            ),
+            expires_at: expires_at.to_rfc3339(),
+            user: Some(user),
+        }))
+    }
+}
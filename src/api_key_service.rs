@@ -0,0 +1,179 @@
+use chrono::{DateTime, Utc};
+use ring::digest;
+use sqlx::PgPool;
+use tonic::{Request, Response, Status};
+
+use crate::audit;
+use crate::auth_service::generate_token;
+use crate::me_extension::{MeExtension, Scope};
+use crate::proto::proto::santa_cruz;
+use crate::proto::proto::santa_cruz::{
+    ApiKeyScope, CreateApiKeyRequest, CreateApiKeyResponse, DeleteApiKeyRequest,
+    DeleteApiKeyResponse, GetApiKeysRequest, GetApiKeysResponse,
+};
+
+const KEY_PREFIX: &str = "sc_";
+const DISPLAYED_PREFIX_LENGTH: usize = 11;
+const NAME_MAX_LENGTH: usize = 100;
+
+pub struct ApiKeyService {
+    pool: PgPool,
+}
+
+type ApiKeyRow = (
+    i32,
+    DateTime<Utc>,
+    DateTime<Utc>,
+    i32,
+    String,
+    String,
+    Vec<String>,
+);
+
+impl From<Scope> for ApiKeyScope {
+    fn from(scope: Scope) -> Self {
+        match scope {
+            Scope::ReadOnly => ApiKeyScope::ReadOnly,
+            Scope::WorkoutsOnly => ApiKeyScope::WorkoutsOnly,
+        }
+    }
+}
+
+impl From<ApiKeyRow> for santa_cruz::ApiKey {
+    fn from(row: ApiKeyRow) -> Self {
+        santa_cruz::ApiKey {
+            id: row.0,
+            created_at: row.1.to_rfc3339(),
+            updated_at: row.2.to_rfc3339(),
+            user_id: row.3,
+            name: row.4,
+            prefix: row.5,
+            scopes: row
+                .6
+                .iter()
+                .filter_map(|scope| Scope::parse(scope))
+                .map(|scope| ApiKeyScope::from(scope) as i32)
+                .collect(),
+        }
+    }
+}
+
+impl ApiKeyService {
+    pub fn new(pool: &PgPool) -> ApiKeyService {
+        ApiKeyService { pool: pool.clone() }
+    }
+}
+
+/// Keys are random enough to be stored with a fast hash, which lets the interceptor look them up.
+pub fn hash_api_key(key: &str) -> String {
+    digest::digest(&digest::SHA256, key.as_bytes())
+        .as_ref()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn parse_scopes(scopes: &[i32]) -> Result<Vec<Scope>, String> {
+    let mut parsed = vec![];
+
+    for value in scopes {
+        let scope = match ApiKeyScope::from_i32(*value) {
+            Some(ApiKeyScope::ReadOnly) => Scope::ReadOnly,
+            Some(ApiKeyScope::WorkoutsOnly) => Scope::WorkoutsOnly,
+            _ => return Err(format!("unknown api key scope {}", value)),
+        };
+
+        if !parsed.contains(&scope) {
+            parsed.push(scope);
+        }
+    }
+
+    Ok(parsed)
+}
+
+#[tonic::async_trait]
+impl santa_cruz::api_key_service_server::ApiKeyService for ApiKeyService {
+    async fn create_api_key(
+        &self,
+        request: Request<CreateApiKeyRequest>,
+    ) -> Result<Response<CreateApiKeyResponse>, Status> {
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+        let CreateApiKeyRequest { name, scopes } = request.get_ref();
+
+        let name = name.trim();
+
+        if name.is_empty() || name.len() > NAME_MAX_LENGTH {
+            return Err(Status::invalid_argument(format!(
+                "name must be between 1 and {} characters",
+                NAME_MAX_LENGTH
+            )));
+        }
+
+        let scopes = parse_scopes(scopes).map_err(Status::invalid_argument)?;
+
+        let key = format!("{}{}", KEY_PREFIX, generate_token());
+
+        let row: ApiKeyRow = sqlx::query_as(
+            r#"INSERT INTO api_keys ( user_id, name, prefix, key_hash, scopes ) VALUES ( $1, $2, $3, $4, $5 ) RETURNING id, created_at, updated_at, user_id, name, prefix, scopes"#,
+        )
+        .bind(user_id)
+        .bind(name)
+        .bind(&key[..DISPLAYED_PREFIX_LENGTH])
+        .bind(hash_api_key(&key))
+        .bind(scopes.iter().map(|scope| scope.as_str()).collect::<Vec<_>>())
+        .fetch_one(&self.pool)
+        .await
+        .expect("create_api_key error");
+
+        audit::record(&self.pool, request.extensions(), "api_keys", row.0, None).await;
+
+        Ok(Response::new(CreateApiKeyResponse {
+            api_key: Some(row.into()),
+            key,
+        }))
+    }
+
+    async fn delete_api_key(
+        &self,
+        request: Request<DeleteApiKeyRequest>,
+    ) -> Result<Response<DeleteApiKeyResponse>, Status> {
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+        let DeleteApiKeyRequest { id } = request.get_ref();
+
+        let before = audit::snapshot(&self.pool, "api_keys", *id).await;
+
+        let result = sqlx::query(r#"DELETE FROM api_keys WHERE id = $1 AND user_id = $2"#)
+            .bind(id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .expect("delete_api_key error");
+
+        if result.rows_affected() == 0 {
+            return Err(Status::not_found(format!("api key #{} not found", id)));
+        }
+
+        audit::record(&self.pool, request.extensions(), "api_keys", *id, before).await;
+
+        Ok(Response::new(DeleteApiKeyResponse {}))
+    }
+
+    async fn get_api_keys(
+        &self,
+        request: Request<GetApiKeysRequest>,
+    ) -> Result<Response<GetApiKeysResponse>, Status> {
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+
+        let rows: Vec<ApiKeyRow> = sqlx::query_as(
+            r#"SELECT id, created_at, updated_at, user_id, name, prefix, scopes FROM api_keys WHERE user_id = $1 ORDER BY created_at DESC"#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .expect("get_api_keys error");
+
+        let api_keys = rows.into_iter().map(|row| row.into()).collect();
+
+        Ok(Response::new(GetApiKeysResponse { api_keys }))
+    }
+}
@@ -0,0 +1,136 @@
+use serde_json::Value;
+use sqlx::types::Json;
+use sqlx::PgPool;
+use tonic::Extensions;
+
+use crate::me_extension::MeExtension;
+use crate::rpc_method::RpcMethod;
+
+/// Columns never copied into the audit log.
+const REDACTED: &[&str] = &[
+    "key_hash",
+    "token",
+    "refresh_token",
+    "password",
+    "totp_secret",
+];
+
+/// Current state of a row as JSON, `None` once it is deleted.
+pub async fn snapshot(pool: &PgPool, table: &str, id: i32) -> Option<Value> {
+    let row: Option<(Json<Value>,)> = sqlx::query_as(&format!(
+        "SELECT to_jsonb(t) FROM {} t WHERE id = $1",
+        table
+    ))
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+    .expect("snapshot error");
+
+    row.map(|row| redact(row.0 .0))
+}
+
+/// Drops the `REDACTED` columns of a row read as JSON.
+pub fn redact(mut value: Value) -> Value {
+    if let Some(object) = value.as_object_mut() {
+        object.retain(|key, _| !REDACTED.contains(&key.as_str()));
+    }
+
+    value
+}
+
+/// Appends a change of `table` row `id` made by the current request, `before` comes from
+/// [`snapshot`] ahead of the change while the state after it is read here.
+pub async fn record(
+    pool: &PgPool,
+    extensions: &Extensions,
+    table: &str,
+    id: i32,
+    before: Option<Value>,
+) {
+    record_for(pool, extensions, 0, table, id, before).await;
+}
+
+/// [`record`] for the requests of `AuthService`, which come before any authentication and are
+/// attributed to the `user_id` they act on.
+pub async fn record_for(
+    pool: &PgPool,
+    extensions: &Extensions,
+    user_id: i32,
+    table: &str,
+    id: i32,
+    before: Option<Value>,
+) {
+    let after = snapshot(pool, table, id).await;
+
+    insert(pool, extensions, user_id, table, id, before, after).await;
+}
+
+/// [`record`] of a change whose effect doesn't show in the row itself, `after` is written as is.
+pub async fn record_with(
+    pool: &PgPool,
+    extensions: &Extensions,
+    table: &str,
+    id: i32,
+    before: Option<Value>,
+    after: Option<Value>,
+) {
+    insert(pool, extensions, 0, table, id, before, after).await;
+}
+
+async fn insert(
+    pool: &PgPool,
+    extensions: &Extensions,
+    user_id: i32,
+    table: &str,
+    id: i32,
+    before: Option<Value>,
+    after: Option<Value>,
+) {
+    let me = extensions.get::<MeExtension>();
+    let actor_id = me.map(|me| me.user_id).unwrap_or(user_id);
+    let impersonator_id = me.and_then(|me| me.impersonator_id);
+
+    let rpc = extensions
+        .get::<RpcMethod>()
+        .map(|rpc| format!("{}/{}", rpc.service, rpc.method))
+        .unwrap_or_default();
+
+    sqlx::query(
+        r#"INSERT INTO audit_log ( actor_id, rpc, resource_type, resource_id, before, after, impersonator_id ) VALUES ( $1, $2, $3, $4, $5, $6, $7 )"#,
+    )
+    .bind(actor_id)
+    .bind(rpc)
+    .bind(table)
+    .bind(id)
+    .bind(before.map(Json))
+    .bind(after.map(Json))
+    .bind(impersonator_id)
+    .execute(pool)
+    .await
+    .expect("record error");
+}
+
+/// Top-level fields whose value differs between two snapshots.
+pub fn changed_fields(before: Option<&Value>, after: Option<&Value>) -> Vec<String> {
+    let fields = |value: Option<&Value>| {
+        value
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default()
+    };
+
+    let before = fields(before);
+    let after = fields(after);
+
+    let mut changed: Vec<String> = before
+        .keys()
+        .chain(after.keys())
+        .filter(|key| before.get(*key) != after.get(*key))
+        .cloned()
+        .collect();
+
+    changed.sort();
+    changed.dedup();
+
+    changed
+}
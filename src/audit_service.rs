@@ -0,0 +1,139 @@
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::types::Json;
+use sqlx::PgPool;
+use tonic::{Request, Response, Status};
+
+use crate::audit;
+use crate::me_extension::{MeExtension, Role};
+use crate::proto::proto::santa_cruz;
+use crate::proto::proto::santa_cruz::{AuditEntry, GetAuditLogRequest, GetAuditLogResponse};
+use crate::sharing;
+use crate::sharing::Level;
+
+const DEFAULT_PAGE_SIZE: i32 = 50;
+const MAX_PAGE_SIZE: i32 = 200;
+
+/// Resources whose whole history is visible to anyone who can view them.
+const SHARED_RESOURCES: &[&str] = &["exercises", "workouts", "workout_sets", "workout_repeats"];
+
+pub struct AuditService {
+    pool: PgPool,
+}
+
+type AuditRow = (
+    i64,
+    DateTime<Utc>,
+    i32,
+    String,
+    String,
+    i32,
+    Option<Json<Value>>,
+    Option<Json<Value>>,
+    Option<i32>,
+);
+
+impl From<AuditRow> for AuditEntry {
+    fn from(row: AuditRow) -> Self {
+        let before = row.6.map(|json| json.0);
+        let after = row.7.map(|json| json.0);
+        let to_string = |value: &Option<Value>| {
+            value
+                .as_ref()
+                .map(|value| value.to_string())
+                .unwrap_or_default()
+        };
+
+        AuditEntry {
+            id: row.0,
+            created_at: row.1.to_rfc3339(),
+            actor_id: row.2,
+            rpc: row.3,
+            resource_type: row.4,
+            resource_id: row.5,
+            changed_fields: audit::changed_fields(before.as_ref(), after.as_ref()),
+            before: to_string(&before),
+            after: to_string(&after),
+            impersonator_id: row.8.unwrap_or_default(),
+        }
+    }
+}
+
+impl AuditService {
+    pub fn new(pool: &PgPool) -> AuditService {
+        AuditService { pool: pool.clone() }
+    }
+
+    async fn can_view_history(&self, me: &MeExtension, resource_type: &str, id: i32) -> bool {
+        if me.role == Role::Admin {
+            return true;
+        }
+
+        SHARED_RESOURCES.contains(&resource_type)
+            && sharing::has_level(&self.pool, resource_type, id, me.user_id, Level::Viewer).await
+    }
+}
+
+#[tonic::async_trait]
+impl santa_cruz::audit_service_server::AuditService for AuditService {
+    async fn get_audit_log(
+        &self,
+        request: Request<GetAuditLogRequest>,
+    ) -> Result<Response<GetAuditLogResponse>, Status> {
+        let me = request.extensions().get::<MeExtension>().unwrap();
+        let GetAuditLogRequest {
+            resource_type,
+            resource_id,
+            page_size,
+            page_token,
+        } = request.get_ref();
+
+        let page_size = match *page_size {
+            size if size <= 0 => DEFAULT_PAGE_SIZE,
+            size => size.min(MAX_PAGE_SIZE),
+        };
+
+        let before_id = match page_token.as_str() {
+            "" => i64::MAX,
+            token => token
+                .parse::<i64>()
+                .map_err(|_| Status::invalid_argument("invalid page token"))?,
+        };
+
+        // without access to the resource only the caller's own changes are listed
+        let actor_id = match self.can_view_history(me, resource_type, *resource_id).await {
+            true => None,
+            false => Some(me.user_id),
+        };
+
+        let rows: Vec<AuditRow> = sqlx::query_as(
+            r#"SELECT id, created_at, actor_id, rpc, resource_type, resource_id, before, after, impersonator_id FROM audit_log WHERE resource_type = $1 AND resource_id = $2 AND id < $3 AND ($4::integer IS NULL OR actor_id = $4) ORDER BY id DESC LIMIT $5"#,
+        )
+        .bind(resource_type)
+        .bind(resource_id)
+        .bind(before_id)
+        .bind(actor_id)
+        .bind(i64::from(page_size) + 1)
+        .fetch_all(&self.pool)
+        .await
+        .expect("get_audit_log error");
+
+        let mut entries: Vec<AuditEntry> = rows.into_iter().map(|row| row.into()).collect();
+
+        let next_page_token = match entries.len() > page_size as usize {
+            true => {
+                entries.truncate(page_size as usize);
+                entries
+                    .last()
+                    .map(|entry| entry.id.to_string())
+                    .unwrap_or_default()
+            }
+            false => String::new(),
+        };
+
+        Ok(Response::new(GetAuditLogResponse {
+            entries,
+            next_page_token,
+        }))
+    }
+}
@@ -1,44 +1,231 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use chrono::{DateTime, Utc};
+use log::info;
 use sqlx::PgPool;
 use tonic::service::Interceptor;
 use tonic::Status;
 
-use crate::me_extension::MeExtension;
+use crate::api_key_service::hash_api_key;
+use crate::jwt::JwtKeys;
+use crate::me_extension::{MeExtension, Role, Scope};
+use crate::policy::authorize;
+use crate::rpc_method::RpcMethod;
+use crate::sessions_cache::{CachedApiKey, CachedSession};
 use crate::SessionsCache;
 
+const API_KEY_HEADER: &str = "x-api-key";
+const WORKOUTS_SERVICES: [&str; 4] = [
+    "ExerciseService",
+    "WorkoutService",
+    "WorkoutSetService",
+    "WorkoutRepeatService",
+];
+
 #[derive(Clone)]
 pub struct AuthInterceptor {
     cache: Arc<SessionsCache>,
+    jwt: Option<Arc<JwtKeys>>,
 }
 
 impl AuthInterceptor {
-    pub fn new(cache: Arc<SessionsCache>) -> AuthInterceptor {
-        AuthInterceptor { cache }
+    pub fn new(cache: Arc<SessionsCache>, jwt: Option<Arc<JwtKeys>>) -> AuthInterceptor {
+        AuthInterceptor { cache, jwt }
+    }
+
+    /// Tokens are self-contained in jwt mode, only the revoked ids have to be looked up.
+    fn authenticate_jwt(&self, jwt: &JwtKeys, token: &str) -> Result<MeExtension, &'static str> {
+        let claims = jwt.verify(token).map_err(|_| "Invalid auth token")?;
+
+        if self.cache.is_revoked(&claims.jti) {
+            return Err("Session revoked");
+        }
+
+        Ok(MeExtension {
+            impersonator_id: claims.act,
+            session_token: Some(claims.jti),
+            ..MeExtension::new(claims.sub, self.cache.role(claims.sub))
+        })
+    }
+
+    fn authenticate_session(&self, token: &str) -> Result<MeExtension, &'static str> {
+        let session = self.cache.get(token.to_string());
+
+        if session.is_none() {
+            return Err("Session not found");
+        }
+
+        let session = session.unwrap();
+
+        if session.is_expired() {
+            self.cache.remove(token.to_string());
+            return Err("Session expired");
+        }
+
+        Ok(MeExtension {
+            impersonator_id: session.impersonator_id,
+            session_token: Some(token.to_string()),
+            ..MeExtension::new(session.user_id, self.cache.role(session.user_id))
+        })
+    }
+
+    fn authenticate_api_key(&self, key: &str) -> Result<MeExtension, &'static str> {
+        let api_key = self
+            .cache
+            .get_api_key(&hash_api_key(key))
+            .ok_or("Api key not found")?;
+
+        // api keys never carry elevated roles
+        Ok(MeExtension {
+            user_id: api_key.user_id,
+            role: Role::User,
+            scopes: api_key.scopes,
+            impersonator_id: None,
+            session_token: None,
+        })
+    }
+}
+
+fn manages_credentials(RpcMethod { service, method }: &RpcMethod) -> bool {
+    match service.as_str() {
+        "ApiKeyService" | "SessionService" => true,
+        "UserService" => method != "Me",
+        _ => false,
     }
 }
 
-fn get_token(token: &str) -> Option<&str> {
-    let (bearer, token) = token.split_at(token.find(' ').unwrap());
+/// Api keys can't manage credentials, and the workouts scope narrows down what else they can call.
+fn authorize_api_key(me: &MeExtension, rpc_method: Option<&RpcMethod>) -> Result<(), &'static str> {
+    let rpc_method = rpc_method.ok_or("Unknown rpc method")?;
+
+    if manages_credentials(rpc_method) {
+        return Err("Api keys can't manage credentials");
+    }
+
+    let RpcMethod { service, .. } = rpc_method;
 
-    if bearer.to_lowercase() != "bearer" {
+    if me.has_scope(Scope::WorkoutsOnly) && !WORKOUTS_SERVICES.contains(&service.as_str()) {
+        return Err("Api key is limited to workouts");
+    }
+
+    Ok(())
+}
+
+/// Impersonating admins act with the user's role, but never on the user's credentials.
+fn authorize_impersonation(
+    me: &MeExtension,
+    rpc_method: Option<&RpcMethod>,
+) -> Result<(), &'static str> {
+    let impersonator_id = match me.impersonator_id {
+        Some(impersonator_id) => impersonator_id,
+        None => return Ok(()),
+    };
+
+    let rpc_method = rpc_method.ok_or("Unknown rpc method")?;
+
+    if manages_credentials(rpc_method) {
+        return Err("Impersonation sessions can't manage credentials");
+    }
+
+    info!(
+        "admin #{} impersonating user #{} calls {}/{}",
+        impersonator_id, me.user_id, rpc_method.service, rpc_method.method
+    );
+
+    Ok(())
+}
+
+fn get_token(header: &str) -> Option<&str> {
+    let (bearer, token) = header.split_once(' ')?;
+
+    if !bearer.eq_ignore_ascii_case("bearer") {
         return None;
     }
 
-    return Some(token.trim());
+    Some(token.trim())
+}
+
+pub async fn fetch_sessions(pool: &PgPool) -> Result<HashMap<String, CachedSession>, sqlx::Error> {
+    let rows: Vec<(String, i32, Option<i32>, DateTime<Utc>)> = sqlx::query_as(
+        r#"SELECT token, user_id, impersonator_id, expires_at FROM sessions WHERE expires_at > NOW()"#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let sessions = rows
+        .into_iter()
+        .map(|row| {
+            (
+                row.0,
+                CachedSession {
+                    user_id: row.1,
+                    impersonator_id: row.2,
+                    expires_at: row.3,
+                },
+            )
+        })
+        .collect();
+
+    Ok(sessions)
+}
+
+pub async fn fetch_revoked(pool: &PgPool) -> Result<HashMap<String, DateTime<Utc>>, sqlx::Error> {
+    let rows: Vec<(String, DateTime<Utc>)> =
+        sqlx::query_as(r#"SELECT jti, expires_at FROM revoked_tokens WHERE expires_at > NOW()"#)
+            .fetch_all(pool)
+            .await?;
+
+    Ok(rows.into_iter().collect())
+}
+
+pub async fn fetch_api_keys(pool: &PgPool) -> Result<HashMap<String, CachedApiKey>, sqlx::Error> {
+    let rows: Vec<(String, i32, Vec<String>)> =
+        sqlx::query_as(r#"SELECT key_hash, user_id, scopes FROM api_keys WHERE user_id IN (SELECT id FROM users WHERE disabled_at IS NULL)"#)
+            .fetch_all(pool)
+            .await?;
+
+    let api_keys = rows
+        .into_iter()
+        .map(|row| {
+            (
+                row.0,
+                CachedApiKey {
+                    user_id: row.1,
+                    scopes: row.2.iter().filter_map(|s| Scope::parse(s)).collect(),
+                },
+            )
+        })
+        .collect();
+
+    Ok(api_keys)
+}
+
+pub async fn fetch_roles(pool: &PgPool) -> Result<HashMap<i32, Role>, sqlx::Error> {
+    let rows: Vec<(i32, String)> =
+        sqlx::query_as(r#"SELECT id, role FROM users WHERE role <> 'user'"#)
+            .fetch_all(pool)
+            .await?;
+
+    let roles = rows
+        .into_iter()
+        .filter_map(|row| Role::parse(&row.1).map(|role| (row.0, role)))
+        .collect();
+
+    Ok(roles)
 }
 
 pub async fn load_sessions(pool: &PgPool) -> Arc<SessionsCache> {
-    let rows: Vec<(String, i32)> = sqlx::query_as(r#"SELECT token, user_id FROM sessions"#)
-        .fetch_all(pool)
-        .await
-        .expect("load_sessions error");
+    let sessions = fetch_sessions(pool).await.expect("load_sessions error");
+    let revoked = fetch_revoked(pool).await.expect("load_sessions error");
+    let api_keys = fetch_api_keys(pool).await.expect("load_sessions error");
+    let roles = fetch_roles(pool).await.expect("load_sessions error");
 
     let cache = Arc::new(SessionsCache::default());
-
-    for row in rows {
-        cache.insert(row.0, row.1);
-    }
+    cache.replace(sessions);
+    cache.replace_revoked(revoked);
+    cache.replace_api_keys(api_keys);
+    cache.replace_roles(roles);
 
     cache
 }
@@ -46,27 +233,46 @@ pub async fn load_sessions(pool: &PgPool) -> Arc<SessionsCache> {
 impl Interceptor for AuthInterceptor {
     fn call(&mut self, request: tonic::Request<()>) -> Result<tonic::Request<()>, Status> {
         let mut req = request;
-        match req.metadata().get("authorization") {
-            Some(t) => {
-                let token = get_token(t.to_str().expect("token should be there"));
+        let rpc_method = req.extensions().get::<RpcMethod>();
 
-                if token.is_none() {
-                    return Err(Status::unauthenticated("No valid auth token"));
-                }
+        let me = match req.metadata().get(API_KEY_HEADER) {
+            Some(key) => {
+                let key = key
+                    .to_str()
+                    .map_err(|_| Status::unauthenticated("No valid api key"))?;
+                let me = self
+                    .authenticate_api_key(key)
+                    .map_err(Status::unauthenticated)?;
 
-                let user_id = self.cache.get(token.unwrap().to_string());
+                authorize_api_key(&me, rpc_method).map_err(Status::permission_denied)?;
 
-                if user_id.is_none() {
-                    return Err(Status::unauthenticated("Session not found"));
-                }
+                me
+            }
+            None => {
+                let token = req
+                    .metadata()
+                    .get("authorization")
+                    .and_then(|header| header.to_str().ok())
+                    .and_then(get_token)
+                    .ok_or_else(|| Status::unauthenticated("No valid auth token"))?;
+
+                let me = match &self.jwt {
+                    Some(jwt) => self.authenticate_jwt(jwt, token),
+                    None => self.authenticate_session(token),
+                };
 
-                req.extensions_mut().insert(MeExtension {
-                    user_id: user_id.unwrap(),
-                });
+                let me = me.map_err(Status::unauthenticated)?;
 
-                Ok(req)
+                authorize_impersonation(&me, rpc_method).map_err(Status::permission_denied)?;
+
+                me
             }
-            _ => Err(Status::unauthenticated("No valid auth token")),
-        }
+        };
+
+        authorize(&me, rpc_method).map_err(Status::permission_denied)?;
+
+        req.extensions_mut().insert(me);
+
+        Ok(req)
     }
 }
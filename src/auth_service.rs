@@ -1,27 +1,252 @@
 use argon2::password_hash::SaltString;
 use argon2::{Argon2, PasswordHash, PasswordHasher, PasswordVerifier};
-use rand_core::OsRng;
+use chrono::{DateTime, Duration, Utc};
+use log::error;
+use rand_core::{OsRng, RngCore};
+use serde_json::Value;
+use sqlx::types::Json;
 use sqlx::PgPool;
+use std::slice;
 use std::sync::Arc;
-use tonic::{Request, Response, Status};
+use tonic::{Extensions, Request, Response, Status};
 
+use crate::audit;
+use crate::jwt::{Claims, JwtKeys};
+use crate::login_throttle::{LoginThrottle, ThrottleKey};
+use crate::mailer::{MailMessage, Mailer};
 use crate::proto::proto::santa_cruz;
-use crate::proto::proto::santa_cruz::{LoginRequest, LogoutRequest, LogoutResponse};
+use crate::proto::proto::santa_cruz::{
+    AuthResponse, LoginRequest, LogoutRequest, LogoutResponse, RefreshSessionRequest,
+    RegisterRequest, RequestPasswordResetRequest, RequestPasswordResetResponse,
+    ResetPasswordRequest, ResetPasswordResponse, VerifyTwoFactorRequest,
+};
+use crate::session_service::revoke_user_sessions;
+use crate::sessions_cache::CachedSession;
+use crate::totp;
 use crate::user_service::get_user_by_id;
 use crate::SessionsCache;
 
+const TOKEN_BYTES: usize = 32;
+const ACCESS_TOKEN_TTL_MINUTES: i64 = 60;
+const REFRESH_TOKEN_TTL_DAYS: i64 = 30;
+const PASSWORD_RESET_TTL_MINUTES: i64 = 60;
+const LOGIN_CHALLENGE_TTL_MINUTES: i64 = 5;
+const LOGIN_CHALLENGE_MAX_ATTEMPTS: i32 = 5;
+const PASSWORD_MIN_LENGTH: usize = 8;
+const EMAIL_MAX_LENGTH: usize = 254;
+/// Hash with the default parameters that logins for unknown emails are checked against.
+const DUMMY_PASSWORD_HASH: &str =
+    "$argon2id$v=19$m=4096,t=3,p=1$rxu5vJIi4h3QU6u2+uGl5A$3sqWF0pVdw1duwOn/l0NHhTbpB/5LLmE7/zZ1e8uafc";
+
 pub struct AuthService {
     pool: PgPool,
     cache: Arc<SessionsCache>,
+    mailer: Arc<dyn Mailer>,
+    throttle: LoginThrottle,
+    jwt: Option<Arc<JwtKeys>>,
 }
 
 impl AuthService {
-    pub fn new(pool: &PgPool, cache: Arc<SessionsCache>) -> AuthService {
+    pub fn new(
+        pool: &PgPool,
+        cache: Arc<SessionsCache>,
+        mailer: Arc<dyn Mailer>,
+        jwt: Option<Arc<JwtKeys>>,
+    ) -> AuthService {
         AuthService {
             pool: pool.clone(),
             cache,
+            mailer,
+            throttle: LoginThrottle::new(pool),
+            jwt,
+        }
+    }
+
+    /// Token of the session behind `access_token`, an expired JWT still names its session.
+    fn session_token(&self, access_token: String) -> String {
+        match &self.jwt {
+            Some(jwt) => jwt
+                .decode(&access_token)
+                .map(|claims| claims.jti)
+                .unwrap_or(access_token),
+            None => access_token,
         }
     }
+
+    async fn create_session(
+        &self,
+        extensions: &Extensions,
+        user_id: i32,
+        device_name: &str,
+    ) -> AuthResponse {
+        let token = generate_token();
+        let expires_at = Utc::now() + Duration::minutes(ACCESS_TOKEN_TTL_MINUTES);
+        let refresh_token = generate_token();
+        let refresh_expires_at = Utc::now() + Duration::days(REFRESH_TOKEN_TTL_DAYS);
+
+        let (id,): (i32,) = sqlx::query_as(
+            r#"INSERT INTO sessions ( user_id, token, device_name, expires_at, refresh_token, refresh_expires_at ) VALUES ( $1 , $2, $3, $4, $5, $6 ) RETURNING id"#,
+        )
+            .bind(user_id)
+            .bind(&token)
+            .bind(device_name)
+            .bind(expires_at)
+            .bind(&refresh_token)
+            .bind(refresh_expires_at)
+            .fetch_one(&self.pool)
+            .await
+            .expect("create session error");
+
+        audit::record_for(&self.pool, extensions, user_id, "sessions", id, None).await;
+
+        self.cache.insert(
+            token.clone(),
+            CachedSession {
+                user_id,
+                impersonator_id: None,
+                expires_at,
+            },
+        );
+
+        auth_response(
+            get_user_by_id(&self.pool, user_id).await,
+            access_token(self.jwt.as_deref(), user_id, None, &token, expires_at),
+            expires_at,
+            refresh_token,
+            refresh_expires_at,
+        )
+    }
+}
+
+impl AuthService {
+    async fn create_login_challenge(&self, user_id: i32, device_name: &str) -> AuthResponse {
+        let challenge_token = generate_token();
+
+        sqlx::query(
+            r#"INSERT INTO login_challenges ( user_id, token, device_name, expires_at ) VALUES ( $1 , $2, $3, $4 )"#,
+        )
+        .bind(user_id)
+        .bind(&challenge_token)
+        .bind(device_name)
+        .bind(Utc::now() + Duration::minutes(LOGIN_CHALLENGE_TTL_MINUTES))
+        .execute(&self.pool)
+        .await
+        .expect("create login challenge error");
+
+        AuthResponse {
+            two_factor_required: true,
+            challenge_token,
+            ..AuthResponse::default()
+        }
+    }
+
+    async fn verify_second_factor(&self, user_id: i32, code: &str) -> bool {
+        let (secret, last_step): (Option<String>, i64) =
+            sqlx::query_as(r#"SELECT totp_secret, totp_last_step FROM users WHERE id = $1"#)
+                .bind(user_id)
+                .fetch_one(&self.pool)
+                .await
+                .expect("verify_second_factor error");
+
+        let step = secret
+            .and_then(|secret| totp::verify(&secret, code, Utc::now().timestamp()))
+            .filter(|step| *step > last_step);
+
+        if let Some(step) = step {
+            // the step guard makes a code usable only once, even for concurrent requests
+            let updated = sqlx::query(
+                r#"UPDATE users SET totp_last_step = $1 WHERE id = $2 AND totp_last_step < $1"#,
+            )
+            .bind(step)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .expect("verify_second_factor error");
+
+            return updated.rows_affected() > 0;
+        }
+
+        let code = totp::normalize_recovery_code(code);
+
+        let recovery_codes: Vec<(i32, String)> = sqlx::query_as(
+            r#"SELECT id, code FROM recovery_codes WHERE user_id = $1 AND used_at IS NULL"#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .expect("verify_second_factor error");
+
+        let recovery_code = recovery_codes
+            .into_iter()
+            .find(|row| verify_password(code.clone(), row.1.clone()).is_ok());
+
+        if let Some((id, _)) = recovery_code {
+            let updated = sqlx::query(
+                r#"UPDATE recovery_codes SET used_at = NOW() WHERE id = $1 AND used_at IS NULL"#,
+            )
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .expect("verify_second_factor error");
+
+            return updated.rows_affected() > 0;
+        }
+
+        false
+    }
+}
+
+fn throttled(delay: Duration) -> Status {
+    Status::resource_exhausted(format!(
+        "too many failed attempts, retry in {} seconds",
+        delay.num_seconds().max(1)
+    ))
+}
+
+/// In jwt mode the session token is kept as the `jti` claim, so sessions can still
+/// be listed, refreshed and revoked; otherwise it is handed out as is.
+pub fn access_token(
+    jwt: Option<&JwtKeys>,
+    user_id: i32,
+    impersonator_id: Option<i32>,
+    token: &str,
+    expires_at: DateTime<Utc>,
+) -> String {
+    match jwt {
+        Some(jwt) => jwt.encode(&Claims {
+            sub: user_id,
+            jti: token.to_string(),
+            iat: Utc::now().timestamp(),
+            exp: expires_at.timestamp(),
+            act: impersonator_id,
+        }),
+        None => token.to_string(),
+    }
+}
+
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    OsRng.fill_bytes(&mut bytes);
+
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn auth_response(
+    user: santa_cruz::User,
+    token: String,
+    expires_at: DateTime<Utc>,
+    refresh_token: String,
+    refresh_expires_at: DateTime<Utc>,
+) -> AuthResponse {
+    AuthResponse {
+        user: Some(user),
+        token,
+        expires_at: expires_at.to_rfc3339(),
+        refresh_token,
+        refresh_expires_at: refresh_expires_at.to_rfc3339(),
+        two_factor_required: false,
+        challenge_token: String::new(),
+    }
 }
 
 pub fn hash_password(password: String) -> Result<String, String> {
@@ -34,6 +259,58 @@ pub fn hash_password(password: String) -> Result<String, String> {
     Ok(pass)
 }
 
+/// Reset tokens handed out before a password change must not outlive it.
+pub async fn expire_password_resets(pool: &PgPool, user_id: i32) {
+    sqlx::query(
+        r#"UPDATE password_resets SET used_at = NOW() WHERE user_id = $1 AND used_at IS NULL"#,
+    )
+    .bind(user_id)
+    .execute(pool)
+    .await
+    .expect("expire_password_resets error");
+}
+
+pub fn normalize_email(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+pub fn validate_email(email: &str) -> Result<(), String> {
+    let invalid = || "email is not valid".to_string();
+
+    if email.is_empty() || email.len() > EMAIL_MAX_LENGTH || email.contains(char::is_whitespace) {
+        return Err(invalid());
+    }
+
+    let (local, domain) = email.rsplit_once('@').ok_or_else(invalid)?;
+
+    if local.is_empty() || local.contains('@') {
+        return Err(invalid());
+    }
+
+    let labels = domain.split('.').collect::<Vec<_>>();
+
+    if labels.len() < 2 || labels.iter().any(|label| label.is_empty()) {
+        return Err(invalid());
+    }
+
+    Ok(())
+}
+
+pub fn validate_password(password: &str) -> Result<(), String> {
+    if password.chars().count() < PASSWORD_MIN_LENGTH {
+        return Err(format!(
+            "password should contain at least {} characters",
+            PASSWORD_MIN_LENGTH
+        ));
+    }
+
+    if !password.chars().any(char::is_alphabetic) || !password.chars().any(|c| c.is_ascii_digit()) {
+        return Err("password should contain both letters and digits".to_string());
+    }
+
+    Ok(())
+}
+
 pub fn verify_password(
     password: String,
     password_hash: String,
@@ -44,83 +321,414 @@ pub fn verify_password(
 
 #[tonic::async_trait]
 impl santa_cruz::auth_service_server::AuthService for AuthService {
+    async fn register(
+        &self,
+        request: Request<RegisterRequest>,
+    ) -> Result<Response<AuthResponse>, Status> {
+        let RegisterRequest {
+            email,
+            password,
+            device_name,
+        } = request.get_ref();
+
+        let email = normalize_email(email);
+
+        validate_email(&email).map_err(Status::invalid_argument)?;
+        validate_password(password).map_err(Status::invalid_argument)?;
+
+        let rec: Option<(i32,)> = sqlx::query_as(
+            r#"INSERT INTO users ( email, password ) VALUES ( $1 , $2 ) ON CONFLICT ((lower(email))) DO NOTHING RETURNING id"#,
+        )
+        .bind(&email)
+        .bind(hash_password(password.to_string()).unwrap())
+        .fetch_optional(&self.pool)
+        .await
+        .expect("register error");
+
+        if rec.is_none() {
+            return Err(Status::already_exists(
+                "user with this email already exists",
+            ));
+        }
+
+        let (user_id,) = rec.unwrap();
+
+        audit::record_for(
+            &self.pool,
+            request.extensions(),
+            user_id,
+            "users",
+            user_id,
+            None,
+        )
+        .await;
+
+        let reply = self
+            .create_session(request.extensions(), user_id, device_name)
+            .await;
+        Ok(Response::new(reply))
+    }
+
     async fn login(
         &self,
         request: Request<LoginRequest>,
-    ) -> Result<Response<santa_cruz::User>, Status> {
+    ) -> Result<Response<AuthResponse>, Status> {
+        let peer = ThrottleKey::peer(request.remote_addr());
         let LoginRequest {
             email,
             password,
-            token,
             device_name,
-        } = &request.into_inner();
+        } = request.get_ref();
+
+        let email = normalize_email(email);
+        let email_key = ThrottleKey::email(&email);
+        let keys = peer
+            .into_iter()
+            .chain([email_key.clone()])
+            .collect::<Vec<_>>();
 
-        let row = sqlx::query_as::<_, (i32, String, String)>(
-            r#"SELECT id, email, password FROM users WHERE email = $1"#,
+        self.throttle.attempt(&keys).await.map_err(throttled)?;
+
+        let row = sqlx::query_as::<_, (i32, String, String, bool, Option<DateTime<Utc>>)>(
+            r#"SELECT id, email, password, totp_enabled, disabled_at FROM users WHERE lower(email) = $1"#,
+        )
+        .bind(&email)
+        .fetch_optional(&self.pool)
+        .await
+        .expect("login error");
+
+        // unknown emails pay for a hash check too, so response times don't reveal accounts
+        let password_hash = row
+            .as_ref()
+            .map(|row| row.2.clone())
+            .unwrap_or_else(|| DUMMY_PASSWORD_HASH.to_string());
+        let verified = verify_password(password.to_string(), password_hash).is_ok();
+        let row = row.filter(|_| verified);
+
+        if row.is_none() {
+            return Err(Status::unauthenticated("invalid email or password"));
+        }
+
+        let (user_id, _, _, totp_enabled, disabled_at) = row.unwrap();
+
+        self.throttle.release(&keys).await;
+
+        if disabled_at.is_some() {
+            return Err(Status::permission_denied("account is disabled"));
+        }
+
+        // with two-factor enabled the counter is only cleared once the second step passes
+        let reply = if totp_enabled {
+            self.create_login_challenge(user_id, device_name).await
+        } else {
+            self.throttle.reset(&email_key).await;
+            self.create_session(request.extensions(), user_id, device_name)
+                .await
+        };
+
+        Ok(Response::new(reply))
+    }
+
+    async fn logout(
+        &self,
+        request: Request<LogoutRequest>,
+    ) -> Result<Response<LogoutResponse>, Status> {
+        let LogoutRequest { user_id, token } = request.get_ref();
+        let token = self.session_token(token.clone());
+
+        let row: Option<(i32, DateTime<Utc>, Json<Value>)> = sqlx::query_as(
+            r#"DELETE FROM sessions WHERE user_id = $1 AND token = $2 RETURNING id, expires_at, to_jsonb(sessions)"#,
+        )
+        .bind(user_id)
+        .bind(&token)
+        .fetch_optional(&self.pool)
+        .await
+        .expect("logout error");
+
+        // the revoked_tokens NOTIFY only has to reach the other instances
+        self.cache.remove(token.clone());
+
+        let (id, expires_at, Json(before)) =
+            row.ok_or_else(|| Status::unauthenticated("session not found"))?;
+
+        self.cache.revoke(token, expires_at);
+
+        audit::record_for(
+            &self.pool,
+            request.extensions(),
+            *user_id,
+            "sessions",
+            id,
+            Some(audit::redact(before)),
         )
-        .bind(email)
-        .fetch_one(&self.pool)
         .await;
 
-        let mut user_id = None;
+        Ok(Response::new(LogoutResponse {}))
+    }
 
-        if row.is_err() {
-            let rec: (i32,) = sqlx::query_as(
-                r#"INSERT INTO users ( email, password ) VALUES ( $1 , $2 ) RETURNING id"#,
-            )
-            .bind(email)
-            .bind(hash_password(password.to_string()).unwrap())
-            .fetch_one(&self.pool)
+    async fn refresh_session(
+        &self,
+        request: Request<RefreshSessionRequest>,
+    ) -> Result<Response<AuthResponse>, Status> {
+        let RefreshSessionRequest { refresh_token } = request.get_ref();
+
+        let token = generate_token();
+        let expires_at = Utc::now() + Duration::minutes(ACCESS_TOKEN_TTL_MINUTES);
+        let next_refresh_token = generate_token();
+        let refresh_expires_at = Utc::now() + Duration::days(REFRESH_TOKEN_TTL_DAYS);
+
+        let row: Option<(i32, i32, String, Json<Value>)> = sqlx::query_as(
+            r#"
+                UPDATE sessions
+                SET token = $1, expires_at = $2, refresh_token = $3, refresh_expires_at = $4, updated_at = NOW()
+                FROM (SELECT * FROM sessions WHERE refresh_token = $5 AND refresh_expires_at > NOW() FOR UPDATE) AS previous
+                WHERE sessions.id = previous.id
+                RETURNING sessions.id, sessions.user_id, previous.token, to_jsonb(previous)
+            "#,
+        )
+            .bind(&token)
+            .bind(expires_at)
+            .bind(&next_refresh_token)
+            .bind(refresh_expires_at)
+            .bind(refresh_token)
+            .fetch_optional(&self.pool)
             .await
-            .expect("create_workout error");
+            .expect("refresh session error");
 
-            user_id = Some(rec.0)
-        } else {
-            let row = row.unwrap();
-            let check_password = verify_password(password.to_string(), row.2);
+        if row.is_none() {
+            return Err(Status::unauthenticated("Refresh token is invalid or expired"));
+        }
 
-            if check_password.is_ok() {
-                user_id = Some(row.0);
-            }
+        let (id, user_id, previous_token, Json(before)) = row.unwrap();
+
+        self.cache.remove(previous_token);
+        self.cache.insert(
+            token.clone(),
+            CachedSession {
+                user_id,
+                impersonator_id: None,
+                expires_at,
+            },
+        );
+
+        audit::record_for(
+            &self.pool,
+            request.extensions(),
+            user_id,
+            "sessions",
+            id,
+            Some(audit::redact(before)),
+        )
+        .await;
+
+        Ok(Response::new(auth_response(
+            get_user_by_id(&self.pool, user_id).await,
+            access_token(self.jwt.as_deref(), user_id, None, &token, expires_at),
+            expires_at,
+            next_refresh_token,
+            refresh_expires_at,
+        )))
+    }
+
+    async fn verify_two_factor(
+        &self,
+        request: Request<VerifyTwoFactorRequest>,
+    ) -> Result<Response<AuthResponse>, Status> {
+        let peer = ThrottleKey::peer(request.remote_addr());
+        let VerifyTwoFactorRequest {
+            challenge_token,
+            code,
+        } = request.get_ref();
+
+        self.throttle
+            .attempt(peer.as_slice())
+            .await
+            .map_err(throttled)?;
+
+        let challenge: Option<(i32, i32, String, String)> = sqlx::query_as(
+            r#"
+                UPDATE login_challenges SET attempts = attempts + 1
+                WHERE token = $1 AND expires_at > NOW() AND attempts < $2
+                RETURNING id, user_id, device_name, (SELECT lower(email) FROM users WHERE users.id = user_id)
+            "#,
+        )
+        .bind(challenge_token)
+        .bind(LOGIN_CHALLENGE_MAX_ATTEMPTS)
+        .fetch_optional(&self.pool)
+        .await
+        .expect("verify_two_factor error");
+
+        if challenge.is_none() {
+            return Err(Status::unauthenticated(
+                "Challenge token is invalid or expired",
+            ));
         }
 
-        if user_id.is_none() {
-            return Err(Status::not_found("user not found"));
+        let (id, user_id, device_name, email) = challenge.unwrap();
+        let email_key = ThrottleKey::email(&email);
+        let keys = peer
+            .iter()
+            .cloned()
+            .chain([email_key.clone()])
+            .collect::<Vec<_>>();
+
+        if let Err(delay) = self.throttle.attempt(slice::from_ref(&email_key)).await {
+            self.throttle.release(peer.as_slice()).await;
+            return Err(throttled(delay));
         }
 
-        let user_id = user_id.unwrap();
+        if !self.verify_second_factor(user_id, code).await {
+            return Err(Status::unauthenticated("code is not valid"));
+        }
 
-        self.cache.insert(token.to_string(), user_id);
+        self.throttle.release(&keys).await;
+        self.throttle.reset(&email_key).await;
 
-        sqlx::query(
-            r#"INSERT INTO sessions ( user_id, token, device_name ) VALUES ( $1 , $2, $3 ) RETURNING id"#,
-        )
-            .bind(user_id)
-            .bind(token)
-            .bind(device_name)
+        sqlx::query(r#"DELETE FROM login_challenges WHERE id = $1"#)
+            .bind(id)
             .execute(&self.pool)
             .await
-            .expect("create session error");
+            .expect("verify_two_factor error");
 
-        let reply = get_user_by_id(&self.pool, user_id).await;
+        let reply = self
+            .create_session(request.extensions(), user_id, &device_name)
+            .await;
         Ok(Response::new(reply))
     }
 
-    async fn logout(
+    async fn request_password_reset(
         &self,
-        request: Request<LogoutRequest>,
-    ) -> Result<Response<LogoutResponse>, Status> {
-        let LogoutRequest { user_id, token } = request.into_inner();
+        request: Request<RequestPasswordResetRequest>,
+    ) -> Result<Response<RequestPasswordResetResponse>, Status> {
+        let RequestPasswordResetRequest { email } = request.get_ref();
+
+        let user: Option<(i32, String)> =
+            sqlx::query_as(r#"SELECT id, email FROM users WHERE lower(email) = $1"#)
+                .bind(normalize_email(email))
+                .fetch_optional(&self.pool)
+                .await
+                .expect("request_password_reset error");
+
+        // unknown emails and failed deliveries get the same reply so the endpoint can't be used
+        // to probe accounts
+        if let Some((user_id, email)) = user {
+            let secret = generate_token();
+            let expires_at = Utc::now() + Duration::minutes(PASSWORD_RESET_TTL_MINUTES);
+
+            let (id,): (i32,) = sqlx::query_as(
+                r#"INSERT INTO password_resets ( user_id, token, expires_at ) VALUES ( $1 , $2, $3 ) RETURNING id"#,
+            )
+            .bind(user_id)
+            .bind(hash_password(secret.clone()).unwrap())
+            .bind(expires_at)
+            .fetch_one(&self.pool)
+            .await
+            .expect("create password reset error");
+
+            audit::record_for(
+                &self.pool,
+                request.extensions(),
+                user_id,
+                "password_resets",
+                id,
+                None,
+            )
+            .await;
+
+            let sent = self
+                .mailer
+                .send(MailMessage {
+                    to: email,
+                    subject: "Password reset".to_string(),
+                    body: format!(
+                        "Use this code to reset your password: {}.{}\nThe code expires at {}.",
+                        id,
+                        secret,
+                        expires_at.to_rfc3339()
+                    ),
+                })
+                .await;
+
+            if let Err(e) = sent {
+                error!("password reset mail to user #{} failed: {}", user_id, e);
+            }
+        }
+
+        Ok(Response::new(RequestPasswordResetResponse {}))
+    }
+
+    async fn reset_password(
+        &self,
+        request: Request<ResetPasswordRequest>,
+    ) -> Result<Response<ResetPasswordResponse>, Status> {
+        let ResetPasswordRequest { token, password } = request.get_ref();
+
+        let invalid = || Status::unauthenticated("Reset token is invalid or expired");
+
+        let (id, secret) = token.split_once('.').ok_or_else(invalid)?;
+        let id = id.parse::<i32>().map_err(|_| invalid())?;
+
+        let row: Option<(i32, String)> = sqlx::query_as(
+            r#"SELECT user_id, token FROM password_resets WHERE id = $1 AND used_at IS NULL AND expires_at > NOW()"#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .expect("reset_password error");
+
+        let user_id = row
+            .filter(|row| verify_password(secret.to_string(), row.1.clone()).is_ok())
+            .map(|row| row.0)
+            .ok_or_else(invalid)?;
+
+        validate_password(password).map_err(Status::invalid_argument)?;
+
+        let before = audit::snapshot(&self.pool, "password_resets", id).await;
 
-        self.cache.remove(token.to_string());
+        let claimed = sqlx::query(
+            r#"UPDATE password_resets SET used_at = NOW() WHERE id = $1 AND used_at IS NULL"#,
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .expect("reset_password error");
+
+        if claimed.rows_affected() == 0 {
+            return Err(invalid());
+        }
+
+        audit::record_for(
+            &self.pool,
+            request.extensions(),
+            user_id,
+            "password_resets",
+            id,
+            before,
+        )
+        .await;
 
-        sqlx::query(r#"DELETE FROM sessions WHERE user_id = $1 AND token = $2 "#)
+        let before = audit::snapshot(&self.pool, "users", user_id).await;
+
+        sqlx::query(r#"UPDATE users SET password = $1, updated_at = NOW() WHERE id = $2"#)
+            .bind(hash_password(password.clone()).unwrap())
             .bind(user_id)
-            .bind(token)
             .execute(&self.pool)
             .await
-            .expect("logout error");
+            .expect("reset_password error");
 
-        Ok(Response::new(LogoutResponse {}))
+        audit::record_for(
+            &self.pool,
+            request.extensions(),
+            user_id,
+            "users",
+            user_id,
+            before,
+        )
+        .await;
+
+        expire_password_resets(&self.pool, user_id).await;
+        revoke_user_sessions(&self.pool, &self.cache, user_id, None).await;
+
+        Ok(Response::new(ResetPasswordResponse {}))
     }
 }
@@ -1,6 +1,6 @@
 use std::env;
 
-use santa_cruz_codegen::{Codegen, CodegenPackage};
+use santa_cruz_codegen::{Codegen, CodegenPackage, FieldKind};
 
 fn main() {
     let mut builder = Codegen::new(concat!(env!("OUT_DIR"), "/file_descriptor_set.bin"));
@@ -13,6 +13,21 @@ fn main() {
         create: Some("CreateExercise"),
         update: Some("UpdateExercise"),
         delete: Some("DeleteExercise"),
+        share: Some("ShareExercise"),
+        unshare: Some("UnshareExercise"),
+        collaborators: Some("ListExerciseCollaborators"),
+        sortable: &[
+            ("created_at", FieldKind::Timestamp),
+            ("updated_at", FieldKind::Timestamp),
+            ("name", FieldKind::Text),
+        ],
+        filterable: &[
+            ("id", FieldKind::Int),
+            ("created_at", FieldKind::Timestamp),
+            ("updated_at", FieldKind::Timestamp),
+            ("name", FieldKind::Text),
+            ("description", FieldKind::Text),
+        ],
         ..CodegenPackage::default()
     });
 
@@ -25,7 +40,27 @@ fn main() {
         create: Some("CreateWorkout"),
         update: Some("UpdateWorkout"),
         delete: Some("DeleteWorkout"),
-        ..CodegenPackage::default()
+        share: Some("ShareWorkout"),
+        unshare: Some("UnshareWorkout"),
+        collaborators: Some("ListWorkoutCollaborators"),
+        tree: Some("GetWorkoutTree"),
+        owner: Some("athlete_id"),
+        sortable: &[
+            ("created_at", FieldKind::Timestamp),
+            ("updated_at", FieldKind::Timestamp),
+            ("status", FieldKind::Text),
+            ("day", FieldKind::Timestamp),
+            ("rate", FieldKind::Int),
+        ],
+        filterable: &[
+            ("id", FieldKind::Int),
+            ("created_at", FieldKind::Timestamp),
+            ("updated_at", FieldKind::Timestamp),
+            ("status", FieldKind::Text),
+            ("day", FieldKind::Timestamp),
+            ("rate", FieldKind::Int),
+            ("comment", FieldKind::Text),
+        ],
     });
 
     let _ = builder.build("src");
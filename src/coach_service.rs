@@ -0,0 +1,280 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Row};
+use tonic::{Request, Response, Status};
+
+use crate::audit;
+use crate::auth_service::normalize_email;
+use crate::me_extension::MeExtension;
+use crate::proto::proto::santa_cruz;
+use crate::proto::proto::santa_cruz::{
+    AcceptCoachRequest, Athlete, CoachRelationship, GetAthletesRequest, GetAthletesResponse,
+    GetCoachRelationshipsRequest, GetCoachRelationshipsResponse, InviteAthleteRequest,
+    RevokeCoachRelationshipRequest, RevokeCoachRelationshipResponse, Workout,
+};
+use crate::sharing::Level;
+use crate::Queryable;
+
+const DEFAULT_RECENT_WORKOUTS: i32 = 5;
+const MAX_RECENT_WORKOUTS: i32 = 50;
+
+const RELATIONSHIP_SELECT: &str = r#"SELECT r.id, r.created_at, r.coach_id, coach.email, r.athlete_id, athlete.email, r.accepted_at FROM coach_athletes r JOIN users coach ON coach.id = r.coach_id JOIN users athlete ON athlete.id = r.athlete_id"#;
+
+pub struct CoachService {
+    pool: PgPool,
+}
+
+type RelationshipRow = (
+    i32,
+    DateTime<Utc>,
+    i32,
+    String,
+    i32,
+    String,
+    Option<DateTime<Utc>>,
+);
+
+impl From<RelationshipRow> for CoachRelationship {
+    fn from(row: RelationshipRow) -> Self {
+        CoachRelationship {
+            id: row.0,
+            created_at: row.1.to_rfc3339(),
+            coach_id: row.2,
+            coach_email: row.3,
+            athlete_id: row.4,
+            athlete_email: row.5,
+            accepted: row.6.is_some(),
+            accepted_at: row.6.map(|at| at.to_rfc3339()).unwrap_or_default(),
+        }
+    }
+}
+
+impl CoachService {
+    pub fn new(pool: &PgPool) -> CoachService {
+        CoachService { pool: pool.clone() }
+    }
+
+    async fn get_relationship_by_id(&self, id: i32) -> CoachRelationship {
+        let row: RelationshipRow =
+            sqlx::query_as(&format!("{} WHERE r.id = $1", RELATIONSHIP_SELECT))
+                .bind(id)
+                .fetch_one(&self.pool)
+                .await
+                .expect("get_relationship_by_id error");
+
+        row.into()
+    }
+
+    /// Workouts owned by each of the athletes, most recently updated first, at most `limit` each.
+    async fn recent_workouts(&self, athlete_ids: &[i32], limit: i32) -> HashMap<i32, Vec<Workout>> {
+        let rows = sqlx::query(&format!(
+            "SELECT * FROM (SELECT {}, CAST(acl.principal as integer) AS athlete_id, ROW_NUMBER() OVER (PARTITION BY acl.principal ORDER BY workouts.updated_at DESC) AS recency FROM workouts JOIN acl ON acl.resource_type = 'workouts' AND acl.resource_id = workouts.id WHERE acl.principal = ANY($1) AND acl.level >= {}) AS recent WHERE recency <= $2 ORDER BY athlete_id, recency",
+            Workout::fields()
+                .iter()
+                .map(|field| format!("workouts.{}", field))
+                .collect::<Vec<_>>()
+                .join(", "),
+            Level::Owner as i32
+        ))
+        .bind(
+            athlete_ids
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>(),
+        )
+        .bind(i64::from(limit))
+        .fetch_all(&self.pool)
+        .await
+        .expect("recent_workouts error");
+
+        let mut workouts: HashMap<i32, Vec<Workout>> = HashMap::new();
+
+        for row in rows {
+            workouts
+                .entry(row.get("athlete_id"))
+                .or_default()
+                .push(row.into());
+        }
+
+        workouts
+    }
+}
+
+#[tonic::async_trait]
+impl santa_cruz::coach_service_server::CoachService for CoachService {
+    async fn invite_athlete(
+        &self,
+        request: Request<InviteAthleteRequest>,
+    ) -> Result<Response<CoachRelationship>, Status> {
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+        let InviteAthleteRequest { email } = request.get_ref();
+
+        let athlete: Option<(i32,)> =
+            sqlx::query_as(r#"SELECT id FROM users WHERE lower(email) = $1"#)
+                .bind(normalize_email(email))
+                .fetch_optional(&self.pool)
+                .await
+                .expect("invite_athlete error");
+
+        let (athlete_id,) = athlete.ok_or_else(|| Status::not_found("user not found"))?;
+
+        if athlete_id == *user_id {
+            return Err(Status::invalid_argument("you can't coach yourself"));
+        }
+
+        let row: Option<(i32,)> = sqlx::query_as(
+            r#"INSERT INTO coach_athletes ( coach_id, athlete_id ) VALUES ( $1, $2 ) ON CONFLICT ( coach_id, athlete_id ) DO NOTHING RETURNING id"#,
+        )
+        .bind(user_id)
+        .bind(athlete_id)
+        .fetch_optional(&self.pool)
+        .await
+        .expect("invite_athlete error");
+
+        let (id,) = row.ok_or_else(|| Status::already_exists("athlete already invited"))?;
+
+        audit::record(&self.pool, request.extensions(), "coach_athletes", id, None).await;
+
+        Ok(Response::new(self.get_relationship_by_id(id).await))
+    }
+
+    async fn accept_coach(
+        &self,
+        request: Request<AcceptCoachRequest>,
+    ) -> Result<Response<CoachRelationship>, Status> {
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+        let AcceptCoachRequest { id } = request.get_ref();
+
+        let before = audit::snapshot(&self.pool, "coach_athletes", *id).await;
+
+        let result = sqlx::query(
+            r#"UPDATE coach_athletes SET accepted_at = NOW(), updated_at = NOW() WHERE id = $1 AND athlete_id = $2 AND accepted_at IS NULL"#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await
+        .expect("accept_coach error");
+
+        if result.rows_affected() == 0 {
+            return Err(Status::not_found(format!("invitation #{} not found", id)));
+        }
+
+        audit::record(
+            &self.pool,
+            request.extensions(),
+            "coach_athletes",
+            *id,
+            before,
+        )
+        .await;
+
+        Ok(Response::new(self.get_relationship_by_id(*id).await))
+    }
+
+    /// Either side can end a relationship or decline a pending invitation.
+    async fn revoke_coach_relationship(
+        &self,
+        request: Request<RevokeCoachRelationshipRequest>,
+    ) -> Result<Response<RevokeCoachRelationshipResponse>, Status> {
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+        let RevokeCoachRelationshipRequest { id } = request.get_ref();
+
+        let before = audit::snapshot(&self.pool, "coach_athletes", *id).await;
+
+        let result = sqlx::query(
+            r#"DELETE FROM coach_athletes WHERE id = $1 AND (coach_id = $2 OR athlete_id = $2)"#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await
+        .expect("revoke_coach_relationship error");
+
+        if result.rows_affected() == 0 {
+            return Err(Status::not_found(format!("relationship #{} not found", id)));
+        }
+
+        audit::record(
+            &self.pool,
+            request.extensions(),
+            "coach_athletes",
+            *id,
+            before,
+        )
+        .await;
+
+        Ok(Response::new(RevokeCoachRelationshipResponse {}))
+    }
+
+    async fn get_coach_relationships(
+        &self,
+        request: Request<GetCoachRelationshipsRequest>,
+    ) -> Result<Response<GetCoachRelationshipsResponse>, Status> {
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+
+        let rows: Vec<RelationshipRow> = sqlx::query_as(&format!(
+            "{} WHERE r.coach_id = $1 OR r.athlete_id = $1 ORDER BY r.created_at DESC",
+            RELATIONSHIP_SELECT
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .expect("get_coach_relationships error");
+
+        let relationships = rows.into_iter().map(|row| row.into()).collect();
+
+        Ok(Response::new(GetCoachRelationshipsResponse {
+            relationships,
+        }))
+    }
+
+    async fn get_athletes(
+        &self,
+        request: Request<GetAthletesRequest>,
+    ) -> Result<Response<GetAthletesResponse>, Status> {
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+        let GetAthletesRequest { recent_workouts } = request.get_ref();
+
+        let limit = match *recent_workouts {
+            limit if limit <= 0 => DEFAULT_RECENT_WORKOUTS,
+            limit => limit.min(MAX_RECENT_WORKOUTS),
+        };
+
+        let rows: Vec<RelationshipRow> = sqlx::query_as(&format!(
+            "{} WHERE r.coach_id = $1 AND r.accepted_at IS NOT NULL ORDER BY athlete.email",
+            RELATIONSHIP_SELECT
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .expect("get_athletes error");
+
+        let relationships = rows
+            .into_iter()
+            .map(CoachRelationship::from)
+            .collect::<Vec<_>>();
+
+        let athlete_ids = relationships
+            .iter()
+            .map(|relationship| relationship.athlete_id)
+            .collect::<Vec<_>>();
+
+        let mut recent_workouts = self.recent_workouts(&athlete_ids, limit).await;
+
+        let athletes = relationships
+            .into_iter()
+            .map(|relationship| Athlete {
+                user_id: relationship.athlete_id,
+                recent_workouts: recent_workouts
+                    .remove(&relationship.athlete_id)
+                    .unwrap_or_default(),
+                email: relationship.athlete_email,
+                accepted_at: relationship.accepted_at,
+            })
+            .collect();
+
+        Ok(Response::new(GetAthletesResponse { athletes }))
+    }
+}
@@ -0,0 +1,30 @@
+use sqlx::PgPool;
+
+pub async fn is_coach(pool: &PgPool, coach_id: i32, athlete_id: i32) -> bool {
+    sqlx::query(
+        r#"SELECT id FROM coach_athletes WHERE coach_id = $1 AND athlete_id = $2 AND accepted_at IS NOT NULL"#,
+    )
+    .bind(coach_id)
+    .bind(athlete_id)
+    .fetch_optional(pool)
+    .await
+    .expect("is_coach error")
+    .is_some()
+}
+
+/// Owner of an object created by `user_id`, coaches may create them on behalf of their athletes.
+pub async fn resolve_owner(
+    pool: &PgPool,
+    user_id: i32,
+    athlete_id: Option<i32>,
+) -> Result<i32, String> {
+    match athlete_id {
+        Some(athlete_id) if athlete_id != user_id => {
+            match is_coach(pool, user_id, athlete_id).await {
+                true => Ok(athlete_id),
+                false => Err(format!("you are not coaching user #{}", athlete_id)),
+            }
+        }
+        _ => Ok(user_id),
+    }
+}
@@ -0,0 +1,11 @@
+use std::fmt::Display;
+
+use log::error;
+use tonic::Status;
+
+/// Logs `error` and answers with a fixed message, details of failed queries stay on the server.
+pub fn internal(error: impl Display) -> Status {
+    error!("{}", error);
+
+    Status::internal("internal error")
+}
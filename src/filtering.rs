@@ -0,0 +1,428 @@
+use chrono::{DateTime, Utc};
+use sqlx::{Encode, Postgres, Type};
+
+use crate::proto::proto::santa_cruz::field_filter::Condition as FieldCondition;
+use crate::proto::proto::santa_cruz::filter::Value;
+use crate::proto::proto::santa_cruz::{
+    float_query, int_query, string_query, FieldFilter, Filter, FloatQuery, IntQuery, StringQuery,
+    TimestampQuery,
+};
+use crate::query_builder::{Condition, QueryBuilder};
+use crate::queryable::FieldKind;
+
+const MAX_DEPTH: usize = 4;
+
+/// Escapes the `LIKE` wildcards of a user supplied value.
+pub fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// Adds `filter` to the conditions of `query_builder`. Only `filterable` fields can be
+/// referenced, each with the query of its kind, and every value is passed as an argument.
+pub fn apply(
+    query_builder: &mut QueryBuilder,
+    filter: Option<&Filter>,
+    filterable: &[(&str, FieldKind)],
+) -> Result<(), String> {
+    if let Some(filter) = filter {
+        let condition = filter_condition(query_builder, filter, filterable, 0)?;
+        query_builder.where_condition(condition);
+    }
+
+    Ok(())
+}
+
+fn filter_condition(
+    query_builder: &mut QueryBuilder,
+    filter: &Filter,
+    filterable: &[(&str, FieldKind)],
+    depth: usize,
+) -> Result<Condition, String> {
+    if depth > MAX_DEPTH {
+        return Err(format!(
+            "filters can't be nested more than {} levels deep",
+            MAX_DEPTH
+        ));
+    }
+
+    let group = |query_builder: &mut QueryBuilder, filters: &[Filter]| {
+        if filters.is_empty() {
+            return Err("filter groups can't be empty".to_string());
+        }
+
+        filters
+            .iter()
+            .map(|filter| filter_condition(query_builder, filter, filterable, depth + 1))
+            .collect::<Result<Vec<Condition>, String>>()
+    };
+
+    match &filter.value {
+        Some(Value::Field(field)) => field_condition(query_builder, field, filterable),
+        Some(Value::And(and)) => group(query_builder, &and.filters).map(Condition::And),
+        Some(Value::Or(or)) => group(query_builder, &or.filters).map(Condition::Or),
+        Some(Value::Not(not)) => filter_condition(query_builder, not, filterable, depth + 1)
+            .map(|condition| Condition::Not(Box::new(condition))),
+        None => Err("empty filter".to_string()),
+    }
+}
+
+fn field_condition(
+    query_builder: &mut QueryBuilder,
+    FieldFilter { field, condition }: &FieldFilter,
+    filterable: &[(&str, FieldKind)],
+) -> Result<Condition, String> {
+    let kind = filterable
+        .iter()
+        .find(|(name, _)| name == field)
+        .map(|(_, kind)| *kind)
+        .ok_or_else(|| format!("can't filter by {}", field))?;
+
+    match (condition, kind) {
+        (Some(FieldCondition::IsNull(true)), _) => Ok(Condition::is_null(field)),
+        (Some(FieldCondition::IsNull(false)), _) => {
+            Ok(Condition::Not(Box::new(Condition::is_null(field))))
+        }
+        (Some(FieldCondition::Integer(query)), FieldKind::Int) => {
+            int_condition(query_builder, field, query)
+        }
+        (Some(FieldCondition::Number(query)), FieldKind::Float) => {
+            float_condition(query_builder, field, query)
+        }
+        (Some(FieldCondition::Text(query)), FieldKind::Text) => {
+            text_condition(query_builder, field, query)
+        }
+        (Some(FieldCondition::Timestamp(query)), FieldKind::Timestamp) => {
+            timestamp_condition(query_builder, field, query)
+        }
+        (Some(_), kind) => Err(format!("{} only accepts {:?} queries", field, kind)),
+        (None, _) => Err(format!("missing condition for {}", field)),
+    }
+}
+
+fn range<'q, V>(
+    query_builder: &mut QueryBuilder,
+    field: &str,
+    bounds: Vec<(&str, Option<V>)>,
+) -> Result<Condition, String>
+where
+    V: Encode<'q, Postgres> + Send + Sync + Type<Postgres> + 'q,
+{
+    let conditions = bounds
+        .into_iter()
+        .filter_map(|(operator, value)| {
+            value.map(|value| query_builder.compare(field, operator, value))
+        })
+        .collect::<Vec<Condition>>();
+
+    match conditions.is_empty() {
+        true => Err(format!("empty range for {}", field)),
+        false => Ok(Condition::And(conditions)),
+    }
+}
+
+fn int_condition(
+    query_builder: &mut QueryBuilder,
+    field: &str,
+    query: &IntQuery,
+) -> Result<Condition, String> {
+    match &query.value {
+        Some(int_query::Value::Eq(eq)) => Ok(query_builder.eq(field, eq.value)),
+        Some(int_query::Value::In(values)) => Ok(query_builder.any(field, values.value.clone())),
+        Some(int_query::Value::Range(bounds)) => range(
+            query_builder,
+            field,
+            vec![
+                (">", bounds.gt),
+                (">=", bounds.gte),
+                ("<", bounds.lt),
+                ("<=", bounds.lte),
+            ],
+        ),
+        None => Err(format!("missing condition for {}", field)),
+    }
+}
+
+fn float_condition(
+    query_builder: &mut QueryBuilder,
+    field: &str,
+    query: &FloatQuery,
+) -> Result<Condition, String> {
+    match &query.value {
+        Some(float_query::Value::Eq(eq)) => Ok(query_builder.eq(field, eq.value)),
+        Some(float_query::Value::In(values)) => Ok(query_builder.any(field, values.value.clone())),
+        Some(float_query::Value::Range(bounds)) => range(
+            query_builder,
+            field,
+            vec![
+                (">", bounds.gt),
+                (">=", bounds.gte),
+                ("<", bounds.lt),
+                ("<=", bounds.lte),
+            ],
+        ),
+        None => Err(format!("missing condition for {}", field)),
+    }
+}
+
+fn text_condition(
+    query_builder: &mut QueryBuilder,
+    field: &str,
+    query: &StringQuery,
+) -> Result<Condition, String> {
+    match &query.value {
+        Some(string_query::Value::Eq(eq)) => Ok(query_builder.eq(field, eq.value.clone())),
+        Some(string_query::Value::In(values)) => Ok(query_builder.any(field, values.value.clone())),
+        Some(string_query::Value::Prefix(prefix)) => {
+            Ok(query_builder.compare(field, "ILIKE", format!("{}%", escape_like(prefix))))
+        }
+        Some(string_query::Value::Contains(part)) => {
+            Ok(query_builder.compare(field, "ILIKE", format!("%{}%", escape_like(part))))
+        }
+        None => Err(format!("missing condition for {}", field)),
+    }
+}
+
+fn timestamp_condition(
+    query_builder: &mut QueryBuilder,
+    field: &str,
+    query: &TimestampQuery,
+) -> Result<Condition, String> {
+    let parse = |value: &Option<String>| {
+        value
+            .as_ref()
+            .map(|value| {
+                DateTime::parse_from_rfc3339(value)
+                    .map(|value| value.with_timezone(&Utc))
+                    .map_err(|_| format!("invalid timestamp {} for {}", value, field))
+            })
+            .transpose()
+    };
+
+    range(
+        query_builder,
+        field,
+        vec![
+            (">", parse(&query.gt)?),
+            (">=", parse(&query.gte)?),
+            ("<", parse(&query.lt)?),
+            ("<=", parse(&query.lte)?),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::proto::santa_cruz::filter::Group;
+    use crate::proto::proto::santa_cruz::int_query::Range;
+
+    const FILTERABLE: &[(&str, FieldKind)] = &[
+        ("rate", FieldKind::Int),
+        ("weight", FieldKind::Float),
+        ("comment", FieldKind::Text),
+        ("day", FieldKind::Timestamp),
+    ];
+
+    fn field(field: &str, condition: FieldCondition) -> Filter {
+        Filter {
+            value: Some(Value::Field(FieldFilter {
+                field: field.to_string(),
+                condition: Some(condition),
+            })),
+        }
+    }
+
+    fn rate_eq(value: i64) -> Filter {
+        field(
+            "rate",
+            FieldCondition::Integer(IntQuery {
+                value: Some(int_query::Value::Eq(int_query::Eq { value })),
+            }),
+        )
+    }
+
+    fn comment(value: string_query::Value) -> Filter {
+        field(
+            "comment",
+            FieldCondition::Text(StringQuery { value: Some(value) }),
+        )
+    }
+
+    fn and(filters: Vec<Filter>) -> Filter {
+        Filter {
+            value: Some(Value::And(Group { filters })),
+        }
+    }
+
+    fn or(filters: Vec<Filter>) -> Filter {
+        Filter {
+            value: Some(Value::Or(Group { filters })),
+        }
+    }
+
+    fn not(filter: Filter) -> Filter {
+        Filter {
+            value: Some(Value::Not(Box::new(filter))),
+        }
+    }
+
+    fn where_sql(filter: &Filter) -> Result<String, String> {
+        let mut query_builder = QueryBuilder::new("workouts");
+        query_builder.field("id");
+        apply(&mut query_builder, Some(filter), FILTERABLE)?;
+
+        let sql = query_builder.select_query().0;
+
+        Ok(sql["SELECT id FROM workouts  WHERE ".len()..].to_string())
+    }
+
+    #[test]
+    fn numbers_placeholders_through_nested_groups() {
+        let filter = and(vec![
+            rate_eq(1),
+            or(vec![
+                comment(string_query::Value::Prefix("a".to_string())),
+                not(field("weight", FieldCondition::IsNull(true))),
+                rate_eq(2),
+            ]),
+            not(rate_eq(3)),
+        ]);
+
+        assert_eq!(
+            where_sql(&filter).unwrap(),
+            "((rate = $1 AND (comment ILIKE $2 OR NOT weight IS NULL OR rate = $3) AND NOT rate = $4))"
+        );
+    }
+
+    #[test]
+    fn renders_each_query_kind() {
+        assert_eq!(
+            where_sql(&field(
+                "rate",
+                FieldCondition::Integer(IntQuery {
+                    value: Some(int_query::Value::Range(Range {
+                        gt: Some(1),
+                        gte: None,
+                        lt: None,
+                        lte: Some(5),
+                    })),
+                }),
+            ))
+            .unwrap(),
+            "((rate > $1 AND rate <= $2))"
+        );
+        assert_eq!(
+            where_sql(&comment(string_query::Value::In(string_query::In {
+                value: vec!["a".to_string()],
+            })))
+            .unwrap(),
+            "(comment = ANY($1))"
+        );
+        assert_eq!(
+            where_sql(&field(
+                "day",
+                FieldCondition::Timestamp(TimestampQuery {
+                    gte: Some("2022-05-01T00:00:00Z".to_string()),
+                    ..TimestampQuery::default()
+                }),
+            ))
+            .unwrap(),
+            "((day >= $1))"
+        );
+        assert_eq!(
+            where_sql(&field("day", FieldCondition::IsNull(false))).unwrap(),
+            "(NOT day IS NULL)"
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_fields_and_mismatched_queries() {
+        assert_eq!(
+            where_sql(&field("permissions", FieldCondition::IsNull(true))).unwrap_err(),
+            "can't filter by permissions"
+        );
+        assert_eq!(
+            where_sql(&field(
+                "comment",
+                FieldCondition::Integer(IntQuery {
+                    value: Some(int_query::Value::Eq(int_query::Eq { value: 1 })),
+                }),
+            ))
+            .unwrap_err(),
+            "comment only accepts Text queries"
+        );
+        assert_eq!(
+            where_sql(&Filter {
+                value: Some(Value::Field(FieldFilter {
+                    field: "rate".to_string(),
+                    condition: None,
+                })),
+            })
+            .unwrap_err(),
+            "missing condition for rate"
+        );
+        assert_eq!(
+            where_sql(&field(
+                "rate",
+                FieldCondition::Integer(IntQuery { value: None })
+            ))
+            .unwrap_err(),
+            "missing condition for rate"
+        );
+        assert_eq!(
+            where_sql(&field(
+                "day",
+                FieldCondition::Timestamp(TimestampQuery {
+                    lt: Some("yesterday".to_string()),
+                    ..TimestampQuery::default()
+                }),
+            ))
+            .unwrap_err(),
+            "invalid timestamp yesterday for day"
+        );
+        assert_eq!(
+            where_sql(&field(
+                "day",
+                FieldCondition::Timestamp(TimestampQuery::default())
+            ))
+            .unwrap_err(),
+            "empty range for day"
+        );
+    }
+
+    #[test]
+    fn rejects_empty_and_deeply_nested_filters() {
+        assert_eq!(
+            where_sql(&Filter { value: None }).unwrap_err(),
+            "empty filter"
+        );
+        assert_eq!(
+            where_sql(&and(vec![])).unwrap_err(),
+            "filter groups can't be empty"
+        );
+        assert_eq!(
+            where_sql(&or(vec![])).unwrap_err(),
+            "filter groups can't be empty"
+        );
+
+        let mut filter = rate_eq(1);
+
+        for _ in 0..MAX_DEPTH {
+            filter = not(filter);
+        }
+
+        assert!(where_sql(&filter).is_ok());
+        assert_eq!(
+            where_sql(&or(vec![filter])).unwrap_err(),
+            "filters can't be nested more than 4 levels deep"
+        );
+    }
+
+    #[test]
+    fn escapes_like_wildcards() {
+        assert_eq!(escape_like(r"50%_off\"), r"50\%\_off\\");
+    }
+}
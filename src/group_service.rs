@@ -0,0 +1,498 @@
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use tonic::{Extensions, Request, Response, Status};
+
+use crate::audit;
+use crate::auth_service::normalize_email;
+use crate::me_extension::MeExtension;
+use crate::proto::proto::santa_cruz;
+use crate::proto::proto::santa_cruz::{
+    AddGroupMemberRequest, CreateGroupRequest, DeleteGroupRequest, DeleteGroupResponse,
+    GetGroupMembersRequest, GetGroupMembersResponse, GetGroupsRequest, GetGroupsResponse, Group,
+    GroupMember, GroupRole, RemoveGroupMemberRequest, UpdateGroupMemberRequest,
+};
+
+const NAME_MAX_LENGTH: usize = 100;
+
+pub struct GroupService {
+    pool: PgPool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum MemberRole {
+    Member,
+    Manager,
+    Owner,
+}
+
+impl MemberRole {
+    fn parse(role: &str) -> MemberRole {
+        match role {
+            "owner" => MemberRole::Owner,
+            "manager" => MemberRole::Manager,
+            _ => MemberRole::Member,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            MemberRole::Member => "member",
+            MemberRole::Manager => "manager",
+            MemberRole::Owner => "owner",
+        }
+    }
+}
+
+impl From<MemberRole> for GroupRole {
+    fn from(role: MemberRole) -> Self {
+        match role {
+            MemberRole::Member => GroupRole::Member,
+            MemberRole::Manager => GroupRole::Manager,
+            MemberRole::Owner => GroupRole::Owner,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum GroupError {
+    NotFound(i32),
+    MemberNotFound(i32),
+    UserNotFound,
+    AlreadyMember,
+    InvalidName,
+    InvalidRole(i32),
+    Forbidden,
+    LastOwner,
+}
+
+impl From<GroupError> for Status {
+    fn from(error: GroupError) -> Self {
+        match error {
+            GroupError::NotFound(id) => Status::not_found(format!("group #{} not found", id)),
+            GroupError::MemberNotFound(id) => {
+                Status::not_found(format!("member #{} not found", id))
+            }
+            GroupError::UserNotFound => Status::not_found("user not found"),
+            GroupError::AlreadyMember => Status::already_exists("user is already a member"),
+            GroupError::InvalidName => Status::invalid_argument(format!(
+                "name must be between 1 and {} characters",
+                NAME_MAX_LENGTH
+            )),
+            GroupError::InvalidRole(role) => {
+                Status::invalid_argument(format!("invalid group role {}", role))
+            }
+            GroupError::Forbidden => {
+                Status::permission_denied("your role in the group doesn't allow this change")
+            }
+            GroupError::LastOwner => Status::failed_precondition("the last owner can't be removed"),
+        }
+    }
+}
+
+type GroupRow = (i32, DateTime<Utc>, DateTime<Utc>, String, String);
+
+impl From<GroupRow> for Group {
+    fn from(row: GroupRow) -> Self {
+        Group {
+            id: row.0,
+            created_at: row.1.to_rfc3339(),
+            updated_at: row.2.to_rfc3339(),
+            name: row.3,
+            role: GroupRole::from(MemberRole::parse(&row.4)) as i32,
+        }
+    }
+}
+
+/// Unspecified roles default to plain members.
+fn parse_role(role: i32) -> Result<MemberRole, GroupError> {
+    match GroupRole::from_i32(role) {
+        Some(GroupRole::Unspecified) | Some(GroupRole::Member) => Ok(MemberRole::Member),
+        Some(GroupRole::Manager) => Ok(MemberRole::Manager),
+        Some(GroupRole::Owner) => Ok(MemberRole::Owner),
+        None => Err(GroupError::InvalidRole(role)),
+    }
+}
+
+/// Managers handle members up to their own role, owners handle everyone.
+fn can_manage(actor: MemberRole, role: MemberRole) -> bool {
+    actor >= MemberRole::Manager && actor >= role
+}
+
+/// Locks the group until the transaction ends so concurrent changes can't remove every owner,
+/// returns the role of `user_id` in it.
+async fn lock_group(
+    tx: &mut Transaction<'_, Postgres>,
+    group_id: i32,
+    user_id: i32,
+) -> Result<MemberRole, GroupError> {
+    sqlx::query(r#"SELECT id FROM groups WHERE id = $1 FOR UPDATE"#)
+        .bind(group_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .expect("lock_group error")
+        .ok_or(GroupError::NotFound(group_id))?;
+
+    member_role(tx, group_id, user_id)
+        .await
+        .map(|(_, role)| role)
+        .ok_or(GroupError::NotFound(group_id))
+}
+
+/// Membership row id and role of `user_id` in the group.
+async fn member_role(
+    tx: &mut Transaction<'_, Postgres>,
+    group_id: i32,
+    user_id: i32,
+) -> Option<(i32, MemberRole)> {
+    let row: Option<(i32, String)> = sqlx::query_as(
+        r#"SELECT id, role FROM group_members WHERE group_id = $1 AND user_id = $2"#,
+    )
+    .bind(group_id)
+    .bind(user_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .expect("member_role error");
+
+    row.map(|(id, role)| (id, MemberRole::parse(&role)))
+}
+
+async fn ensure_owner_left(
+    tx: &mut Transaction<'_, Postgres>,
+    group_id: i32,
+) -> Result<(), GroupError> {
+    let (owners,): (i64,) =
+        sqlx::query_as(r#"SELECT COUNT(*) FROM group_members WHERE group_id = $1 AND role = $2"#)
+            .bind(group_id)
+            .bind(MemberRole::Owner.as_str())
+            .fetch_one(&mut *tx)
+            .await
+            .expect("ensure_owner_left error");
+
+    match owners {
+        0 => Err(GroupError::LastOwner),
+        _ => Ok(()),
+    }
+}
+
+impl GroupService {
+    pub fn new(pool: &PgPool) -> GroupService {
+        GroupService { pool: pool.clone() }
+    }
+
+    async fn members(&self, group_id: i32) -> Vec<GroupMember> {
+        let rows: Vec<(i32, String, String)> = sqlx::query_as(
+            r#"SELECT u.id, u.email, m.role FROM group_members m JOIN users u ON u.id = m.user_id WHERE m.group_id = $1 ORDER BY u.email"#,
+        )
+        .bind(group_id)
+        .fetch_all(&self.pool)
+        .await
+        .expect("members error");
+
+        rows.into_iter()
+            .map(|(user_id, email, role)| GroupMember {
+                user_id,
+                email,
+                role: GroupRole::from(MemberRole::parse(&role)) as i32,
+            })
+            .collect()
+    }
+
+    async fn add_member(
+        &self,
+        extensions: &Extensions,
+        user_id: i32,
+        group_id: i32,
+        email: &str,
+        role: i32,
+    ) -> Result<(), GroupError> {
+        let role = parse_role(role)?;
+
+        let mut tx = self.pool.begin().await.expect("add_group_member error");
+
+        if !can_manage(lock_group(&mut tx, group_id, user_id).await?, role) {
+            return Err(GroupError::Forbidden);
+        }
+
+        let target: Option<(i32,)> =
+            sqlx::query_as(r#"SELECT id FROM users WHERE lower(email) = $1"#)
+                .bind(normalize_email(email))
+                .fetch_optional(&mut tx)
+                .await
+                .expect("add_group_member error");
+
+        let (member_id,) = target.ok_or(GroupError::UserNotFound)?;
+
+        let row: Option<(i32,)> = sqlx::query_as(
+            r#"INSERT INTO group_members ( group_id, user_id, role ) VALUES ( $1, $2, $3 ) ON CONFLICT ( group_id, user_id ) DO NOTHING RETURNING id"#,
+        )
+        .bind(group_id)
+        .bind(member_id)
+        .bind(role.as_str())
+        .fetch_optional(&mut tx)
+        .await
+        .expect("add_group_member error");
+
+        let (id,) = row.ok_or(GroupError::AlreadyMember)?;
+
+        tx.commit().await.expect("add_group_member error");
+
+        audit::record(&self.pool, extensions, "group_members", id, None).await;
+
+        Ok(())
+    }
+
+    async fn update_member(
+        &self,
+        extensions: &Extensions,
+        user_id: i32,
+        group_id: i32,
+        member_id: i32,
+        role: i32,
+    ) -> Result<(), GroupError> {
+        let role = parse_role(role)?;
+
+        let mut tx = self.pool.begin().await.expect("update_group_member error");
+
+        let actor = lock_group(&mut tx, group_id, user_id).await?;
+        let (id, current) = member_role(&mut tx, group_id, member_id)
+            .await
+            .ok_or(GroupError::MemberNotFound(member_id))?;
+
+        if !can_manage(actor, current.max(role)) {
+            return Err(GroupError::Forbidden);
+        }
+
+        let before = audit::snapshot(&self.pool, "group_members", id).await;
+
+        sqlx::query(
+            r#"UPDATE group_members SET role = $1, updated_at = NOW() WHERE group_id = $2 AND user_id = $3"#,
+        )
+        .bind(role.as_str())
+        .bind(group_id)
+        .bind(member_id)
+        .execute(&mut tx)
+        .await
+        .expect("update_group_member error");
+
+        ensure_owner_left(&mut tx, group_id).await?;
+
+        tx.commit().await.expect("update_group_member error");
+
+        audit::record(&self.pool, extensions, "group_members", id, before).await;
+
+        Ok(())
+    }
+
+    async fn remove_member(
+        &self,
+        extensions: &Extensions,
+        user_id: i32,
+        group_id: i32,
+        member_id: i32,
+    ) -> Result<(), GroupError> {
+        let mut tx = self.pool.begin().await.expect("remove_group_member error");
+
+        let actor = lock_group(&mut tx, group_id, user_id).await?;
+        let (id, current) = member_role(&mut tx, group_id, member_id)
+            .await
+            .ok_or(GroupError::MemberNotFound(member_id))?;
+
+        // anyone can leave a group
+        if member_id != user_id && !can_manage(actor, current) {
+            return Err(GroupError::Forbidden);
+        }
+
+        let before = audit::snapshot(&self.pool, "group_members", id).await;
+
+        sqlx::query(r#"DELETE FROM group_members WHERE group_id = $1 AND user_id = $2"#)
+            .bind(group_id)
+            .bind(member_id)
+            .execute(&mut tx)
+            .await
+            .expect("remove_group_member error");
+
+        ensure_owner_left(&mut tx, group_id).await?;
+
+        tx.commit().await.expect("remove_group_member error");
+
+        audit::record(&self.pool, extensions, "group_members", id, before).await;
+
+        Ok(())
+    }
+}
+
+#[tonic::async_trait]
+impl santa_cruz::group_service_server::GroupService for GroupService {
+    async fn create_group(
+        &self,
+        request: Request<CreateGroupRequest>,
+    ) -> Result<Response<Group>, Status> {
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+        let CreateGroupRequest { name } = request.get_ref();
+
+        let name = name.trim();
+
+        if name.is_empty() || name.len() > NAME_MAX_LENGTH {
+            return Err(GroupError::InvalidName.into());
+        }
+
+        let mut tx = self.pool.begin().await.expect("create_group error");
+
+        let (id, created_at, updated_at, name): (i32, DateTime<Utc>, DateTime<Utc>, String) =
+            sqlx::query_as(
+                r#"INSERT INTO groups ( name ) VALUES ( $1 ) RETURNING id, created_at, updated_at, name"#,
+            )
+            .bind(name)
+            .fetch_one(&mut tx)
+            .await
+            .expect("create_group error");
+
+        sqlx::query(
+            r#"INSERT INTO group_members ( group_id, user_id, role ) VALUES ( $1, $2, $3 )"#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(MemberRole::Owner.as_str())
+        .execute(&mut tx)
+        .await
+        .expect("create_group error");
+
+        tx.commit().await.expect("create_group error");
+
+        audit::record(&self.pool, request.extensions(), "groups", id, None).await;
+
+        let row: GroupRow = (
+            id,
+            created_at,
+            updated_at,
+            name,
+            MemberRole::Owner.as_str().to_string(),
+        );
+
+        Ok(Response::new(row.into()))
+    }
+
+    async fn delete_group(
+        &self,
+        request: Request<DeleteGroupRequest>,
+    ) -> Result<Response<DeleteGroupResponse>, Status> {
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+        let DeleteGroupRequest { id } = request.get_ref();
+
+        let before = audit::snapshot(&self.pool, "groups", *id).await;
+
+        let result = sqlx::query(
+            r#"DELETE FROM groups WHERE id = $1 AND id IN (SELECT group_id FROM group_members WHERE user_id = $2 AND role = $3)"#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(MemberRole::Owner.as_str())
+        .execute(&self.pool)
+        .await
+        .expect("delete_group error");
+
+        if result.rows_affected() == 0 {
+            return Err(GroupError::NotFound(*id).into());
+        }
+
+        audit::record(&self.pool, request.extensions(), "groups", *id, before).await;
+
+        Ok(Response::new(DeleteGroupResponse {}))
+    }
+
+    async fn get_groups(
+        &self,
+        request: Request<GetGroupsRequest>,
+    ) -> Result<Response<GetGroupsResponse>, Status> {
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+
+        let rows: Vec<GroupRow> = sqlx::query_as(
+            r#"SELECT g.id, g.created_at, g.updated_at, g.name, m.role FROM groups g JOIN group_members m ON m.group_id = g.id WHERE m.user_id = $1 ORDER BY g.name"#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .expect("get_groups error");
+
+        let groups = rows.into_iter().map(|row| row.into()).collect();
+
+        Ok(Response::new(GetGroupsResponse { groups }))
+    }
+
+    async fn get_group_members(
+        &self,
+        request: Request<GetGroupMembersRequest>,
+    ) -> Result<Response<GetGroupMembersResponse>, Status> {
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+        let GetGroupMembersRequest { group_id } = request.get_ref();
+
+        let members = self.members(*group_id).await;
+
+        if !members.iter().any(|member| member.user_id == *user_id) {
+            return Err(GroupError::NotFound(*group_id).into());
+        }
+
+        Ok(Response::new(GetGroupMembersResponse { members }))
+    }
+
+    async fn add_group_member(
+        &self,
+        request: Request<AddGroupMemberRequest>,
+    ) -> Result<Response<GetGroupMembersResponse>, Status> {
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+        let AddGroupMemberRequest {
+            group_id,
+            email,
+            role,
+        } = request.get_ref();
+
+        self.add_member(request.extensions(), *user_id, *group_id, email, *role)
+            .await?;
+
+        Ok(Response::new(GetGroupMembersResponse {
+            members: self.members(*group_id).await,
+        }))
+    }
+
+    async fn update_group_member(
+        &self,
+        request: Request<UpdateGroupMemberRequest>,
+    ) -> Result<Response<GetGroupMembersResponse>, Status> {
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+        let UpdateGroupMemberRequest {
+            group_id,
+            member_id,
+            role,
+        } = request.get_ref();
+
+        self.update_member(request.extensions(), *user_id, *group_id, *member_id, *role)
+            .await?;
+
+        Ok(Response::new(GetGroupMembersResponse {
+            members: self.members(*group_id).await,
+        }))
+    }
+
+    async fn remove_group_member(
+        &self,
+        request: Request<RemoveGroupMemberRequest>,
+    ) -> Result<Response<GetGroupMembersResponse>, Status> {
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+        let RemoveGroupMemberRequest {
+            group_id,
+            member_id,
+        } = request.get_ref();
+
+        self.remove_member(request.extensions(), *user_id, *group_id, *member_id)
+            .await?;
+
+        // members leaving the group can't see it anymore
+        if member_id == user_id {
+            return Ok(Response::new(GetGroupMembersResponse { members: vec![] }));
+        }
+
+        Ok(Response::new(GetGroupMembersResponse {
+            members: self.members(*group_id).await,
+        }))
+    }
+}
@@ -0,0 +1,172 @@
+use std::net::SocketAddr;
+
+use chrono::{DateTime, Duration, Utc};
+use log::debug;
+use sqlx::PgPool;
+
+const BASE_DELAY_SECONDS: i64 = 1;
+const MAX_DELAY_SECONDS: i64 = 15 * 60;
+const FAILURES_WINDOW_MINUTES: i64 = 60;
+
+#[derive(Debug, Clone)]
+pub struct ThrottleKey {
+    key: String,
+    free_attempts: i32,
+}
+
+impl ThrottleKey {
+    pub fn email(email: &str) -> ThrottleKey {
+        ThrottleKey {
+            key: format!("email:{}", email),
+            free_attempts: 3,
+        }
+    }
+
+    /// Peers get more room than accounts, many users may share one address behind a NAT.
+    pub fn peer(addr: Option<SocketAddr>) -> Option<ThrottleKey> {
+        addr.map(|addr| ThrottleKey {
+            key: format!("peer:{}", addr.ip()),
+            free_attempts: 20,
+        })
+    }
+
+    /// Doubles with every failure past the free attempts, up to `MAX_DELAY_SECONDS`.
+    fn delay(&self, failures: i32) -> Option<Duration> {
+        let exceeded = failures - self.free_attempts;
+
+        if exceeded < 0 {
+            return None;
+        }
+
+        let seconds = (BASE_DELAY_SECONDS << exceeded.min(10)).min(MAX_DELAY_SECONDS);
+
+        Some(Duration::seconds(seconds))
+    }
+}
+
+#[derive(Clone)]
+pub struct LoginThrottle {
+    pool: PgPool,
+}
+
+impl LoginThrottle {
+    pub fn new(pool: &PgPool) -> LoginThrottle {
+        LoginThrottle { pool: pool.clone() }
+    }
+
+    /// Counts an attempt against every key, unless one of them is locked, in which case the
+    /// time left until the longest lock is lifted is returned and nothing is counted. Attempts
+    /// are counted as failures up front, so parallel requests can't all pass the check before
+    /// a lock is written: each upsert holds its row until the transaction ends. Attempts that
+    /// turn out to be valid are handed back with `release`.
+    pub async fn attempt(&self, keys: &[ThrottleKey]) -> Result<(), Duration> {
+        let mut tx = self.pool.begin().await.expect("login_throttle error");
+        let mut locked_until: Option<DateTime<Utc>> = None;
+
+        for key in keys {
+            let (failures, key_locked_until): (i32, Option<DateTime<Utc>>) = sqlx::query_as(
+                r#"
+                    INSERT INTO login_attempts ( key, failures, last_failure_at ) VALUES ( $1, 1, NOW() )
+                    ON CONFLICT (key) DO UPDATE SET
+                        failures = CASE
+                            WHEN login_attempts.locked_until > NOW() THEN login_attempts.failures
+                            WHEN login_attempts.last_failure_at < NOW() - make_interval(mins => $2) THEN 1
+                            ELSE login_attempts.failures + 1
+                        END,
+                        last_failure_at = CASE
+                            WHEN login_attempts.locked_until > NOW() THEN login_attempts.last_failure_at
+                            ELSE NOW()
+                        END
+                    RETURNING failures, CASE WHEN locked_until > NOW() THEN locked_until END
+                "#,
+            )
+            .bind(&key.key)
+            .bind(FAILURES_WINDOW_MINUTES as i32)
+            .fetch_one(&mut tx)
+            .await
+            .expect("login_throttle error");
+
+            if key_locked_until.is_some() {
+                locked_until = locked_until.max(key_locked_until);
+                continue;
+            }
+
+            if let Some(delay) = key.delay(failures) {
+                debug!("{} locked for {}s", key.key, delay.num_seconds());
+
+                sqlx::query(r#"UPDATE login_attempts SET locked_until = $1 WHERE key = $2"#)
+                    .bind(Utc::now() + delay)
+                    .bind(&key.key)
+                    .execute(&mut tx)
+                    .await
+                    .expect("login_throttle error");
+            }
+        }
+
+        match locked_until {
+            Some(locked_until) => {
+                tx.rollback().await.expect("login_throttle error");
+                Err(locked_until - Utc::now())
+            }
+            None => {
+                tx.commit().await.expect("login_throttle error");
+                Ok(())
+            }
+        }
+    }
+
+    /// Takes back an attempt counted by `attempt`, lifting the lock it may have set.
+    pub async fn release(&self, keys: &[ThrottleKey]) {
+        for key in keys {
+            sqlx::query(
+                r#"
+                    UPDATE login_attempts SET
+                        failures = failures - 1,
+                        locked_until = CASE WHEN failures - 1 < $2 THEN NULL ELSE locked_until END
+                    WHERE key = $1 AND failures > 0
+                "#,
+            )
+            .bind(&key.key)
+            .bind(key.free_attempts)
+            .execute(&self.pool)
+            .await
+            .expect("login_throttle error");
+        }
+    }
+
+    pub async fn reset(&self, key: &ThrottleKey) {
+        sqlx::query(r#"DELETE FROM login_attempts WHERE key = $1"#)
+            .bind(&key.key)
+            .execute(&self.pool)
+            .await
+            .expect("login_throttle error");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delay_starts_after_free_attempts() {
+        let key = ThrottleKey::email("a@b.c");
+
+        assert_eq!(key.delay(0), None);
+        assert_eq!(key.delay(2), None);
+        assert_eq!(key.delay(3), Some(Duration::seconds(1)));
+        assert_eq!(key.delay(6), Some(Duration::seconds(8)));
+    }
+
+    #[test]
+    fn delay_is_capped_for_large_failure_counts() {
+        let email = ThrottleKey::email("a@b.c");
+        let peer = ThrottleKey::peer(Some("127.0.0.1:1".parse().unwrap())).unwrap();
+        let max = Some(Duration::seconds(MAX_DELAY_SECONDS));
+
+        assert_eq!(email.delay(13), max);
+        assert_eq!(email.delay(66), max);
+        assert_eq!(email.delay(67), max);
+        assert_eq!(peer.delay(83), max);
+        assert_eq!(peer.delay(i32::MAX), max);
+    }
+}
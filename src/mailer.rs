@@ -0,0 +1,67 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+
+use chrono::Utc;
+use log::info;
+
+#[derive(Debug, Clone)]
+pub struct MailMessage {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+#[tonic::async_trait]
+pub trait Mailer: Send + Sync + 'static {
+    async fn send(&self, message: MailMessage) -> Result<(), String>;
+}
+
+/// Writes outgoing mail to the application log, for local development.
+#[derive(Debug, Default)]
+pub struct LogMailer {}
+
+#[tonic::async_trait]
+impl Mailer for LogMailer {
+    async fn send(&self, message: MailMessage) -> Result<(), String> {
+        info!(
+            "mail to {} ({}):\n{}",
+            message.to, message.subject, message.body
+        );
+
+        Ok(())
+    }
+}
+
+/// Appends outgoing mail to a local file so it can be inspected by developers and tests.
+#[derive(Debug)]
+pub struct FileMailer {
+    path: PathBuf,
+}
+
+impl FileMailer {
+    pub fn new(path: impl Into<PathBuf>) -> FileMailer {
+        FileMailer { path: path.into() }
+    }
+}
+
+#[tonic::async_trait]
+impl Mailer for FileMailer {
+    async fn send(&self, message: MailMessage) -> Result<(), String> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| e.to_string())?;
+
+        writeln!(
+            file,
+            "Date: {}\nTo: {}\nSubject: {}\n\n{}\n",
+            Utc::now().to_rfc3339(),
+            message.to,
+            message.subject,
+            message.body
+        )
+        .map_err(|e| e.to_string())
+    }
+}
@@ -1,40 +1,83 @@
 use std::env;
+use std::sync::Arc;
 
 use dotenv::dotenv;
 use log::debug;
 use sqlx::PgPool;
-use tonic::transport::Server;
+use tonic::transport::{Body, Server};
 use tonic_web;
+use tower::util::MapRequestLayer;
 
+use crate::admin_service::AdminService;
+use crate::api_key_service::ApiKeyService;
+use crate::audit_service::AuditService;
 use crate::auth_interceptor::{load_sessions, AuthInterceptor};
 use crate::auth_service::AuthService;
+use crate::coach_service::CoachService;
+use crate::group_service::GroupService;
+use crate::jwt::{JwtAlgorithm, JwtKeys};
+use crate::mailer::{FileMailer, LogMailer, Mailer};
+use crate::proto::proto::santa_cruz::admin_service_server::AdminServiceServer;
+use crate::proto::proto::santa_cruz::api_key_service_server::ApiKeyServiceServer;
+use crate::proto::proto::santa_cruz::audit_service_server::AuditServiceServer;
 use crate::proto::proto::santa_cruz::auth_service_server::AuthServiceServer;
+use crate::proto::proto::santa_cruz::coach_service_server::CoachServiceServer;
 use crate::proto::proto::santa_cruz::exercise_service_server::ExerciseServiceServer;
+use crate::proto::proto::santa_cruz::group_service_server::GroupServiceServer;
+use crate::proto::proto::santa_cruz::public_service_server::PublicServiceServer;
+use crate::proto::proto::santa_cruz::session_service_server::SessionServiceServer;
+use crate::proto::proto::santa_cruz::share_link_service_server::ShareLinkServiceServer;
 use crate::proto::proto::santa_cruz::user_service_server::UserServiceServer;
 use crate::proto::proto::santa_cruz::workout_repeat_service_server::WorkoutRepeatServiceServer;
 use crate::proto::proto::santa_cruz::workout_service_server::WorkoutServiceServer;
 use crate::proto::proto::santa_cruz::workout_set_service_server::WorkoutSetServiceServer;
+use crate::public_service::PublicService;
 use crate::queryable::Queryable;
+use crate::rpc_method::insert_rpc_method;
 use crate::services::exercise::ExerciseService;
 use crate::services::workout::WorkoutService;
+use crate::session_service::SessionService;
 use crate::sessions_cache::SessionsCache;
+use crate::sessions_sync::spawn_sessions_sync;
+use crate::share_link_service::ShareLinkService;
 use crate::user_service::UserService;
 use crate::workout_repeat_service::WorkoutRepeatService;
 use crate::workout_set_service::WorkoutSetService;
 
+mod admin_service;
+mod api_key_service;
+mod audit;
+mod audit_service;
 mod auth_interceptor;
 mod auth_service;
+mod coach_service;
+mod coaching;
+mod errors;
+mod filtering;
+mod group_service;
+mod jwt;
+mod login_throttle;
+mod mailer;
 mod me_extension;
+mod pagination;
+mod policy;
 mod proto;
+mod public_service;
 mod query_builder;
 mod queryable;
+mod rpc_method;
 mod services;
 mod session_service;
 mod sessions_cache;
+mod sessions_sync;
+mod share_link_service;
+mod sharing;
+mod sorting;
+mod totp;
 mod user_service;
 mod workout_repeat_service;
-mod workout_service;
 mod workout_set_service;
+mod workout_tree;
 
 #[tokio::main]
 async fn main() {
@@ -46,6 +89,27 @@ async fn main() {
         .expect("postgresql fails");
 
     let cache = load_sessions(&pool).await;
+    spawn_sessions_sync(&pool, cache.clone());
+
+    let mailer: Arc<dyn Mailer> = match env::var("MAIL_OUTBOX") {
+        Ok(path) => Arc::new(FileMailer::new(path)),
+        Err(_) => Arc::new(LogMailer::default()),
+    };
+
+    let jwt = match env::var("AUTH_MODE").as_deref() {
+        Ok("jwt") => {
+            let algorithm = env::var("JWT_ALGORITHM").unwrap_or_else(|_| "HS256".to_string());
+            let algorithm = JwtAlgorithm::parse(&algorithm).expect("JWT_ALGORITHM is not valid");
+            let key_file = env::var("JWT_KEY_FILE").expect("JWT_KEY_FILE must be set");
+
+            Some(Arc::new(
+                JwtKeys::from_file(algorithm, key_file).expect("cannot load jwt key"),
+            ))
+        }
+        _ => None,
+    };
+
+    let interceptor = AuthInterceptor::new(cache.clone(), jwt.clone());
 
     let addr = "[::1]:50051";
     debug!("started: {}", addr);
@@ -53,37 +117,85 @@ async fn main() {
     let auth = tonic_web::config().enable(AuthServiceServer::new(AuthService::new(
         &pool,
         cache.clone(),
+        mailer.clone(),
+        jwt.clone(),
     )));
 
+    let public = tonic_web::config().enable(PublicServiceServer::new(PublicService::new(&pool)));
+
     let user = tonic_web::config().enable(UserServiceServer::with_interceptor(
-        UserService::new(&pool),
-        AuthInterceptor::new(cache.clone()),
+        UserService::new(&pool, cache.clone()),
+        interceptor.clone(),
+    ));
+
+    let session = tonic_web::config().enable(SessionServiceServer::with_interceptor(
+        SessionService::new(&pool, cache.clone()),
+        interceptor.clone(),
+    ));
+
+    let api_key = tonic_web::config().enable(ApiKeyServiceServer::with_interceptor(
+        ApiKeyService::new(&pool),
+        interceptor.clone(),
+    ));
+
+    let share_link = tonic_web::config().enable(ShareLinkServiceServer::with_interceptor(
+        ShareLinkService::new(&pool),
+        interceptor.clone(),
+    ));
+
+    let coach = tonic_web::config().enable(CoachServiceServer::with_interceptor(
+        CoachService::new(&pool),
+        interceptor.clone(),
+    ));
+
+    let group = tonic_web::config().enable(GroupServiceServer::with_interceptor(
+        GroupService::new(&pool),
+        interceptor.clone(),
+    ));
+
+    let admin = tonic_web::config().enable(AdminServiceServer::with_interceptor(
+        AdminService::new(&pool, cache.clone(), jwt.clone()),
+        interceptor.clone(),
+    ));
+
+    let audit = tonic_web::config().enable(AuditServiceServer::with_interceptor(
+        AuditService::new(&pool),
+        interceptor.clone(),
     ));
 
     let exercise = tonic_web::config().enable(ExerciseServiceServer::with_interceptor(
         ExerciseService::new(&pool),
-        AuthInterceptor::new(cache.clone()),
+        interceptor.clone(),
     ));
 
     let workout = tonic_web::config().enable(WorkoutServiceServer::with_interceptor(
         WorkoutService::new(&pool),
-        AuthInterceptor::new(cache.clone()),
+        interceptor.clone(),
     ));
 
     let workout_repeat = tonic_web::config().enable(WorkoutRepeatServiceServer::with_interceptor(
         WorkoutRepeatService::new(&pool),
-        AuthInterceptor::new(cache.clone()),
+        interceptor.clone(),
     ));
 
     let workout_set = tonic_web::config().enable(WorkoutSetServiceServer::with_interceptor(
         WorkoutSetService::new(&pool),
-        AuthInterceptor::new(cache.clone()),
+        interceptor.clone(),
     ));
 
     Server::builder()
         .accept_http1(true)
+        .layer(MapRequestLayer::new(insert_rpc_method::<Body>))
         .add_service(auth)
+        .add_service(public)
         .add_service(user)
+        .add_service(session)
+        .add_service(api_key)
+        .add_service(share_link)
+        .add_service(coach)
+        .add_service(group)
+        .add_service(audit)
+        .add_service(admin)
         .add_service(exercise)
         .add_service(workout)
         .add_service(workout_repeat)
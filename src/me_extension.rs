@@ -1,3 +1,75 @@
+use crate::policy::Access;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    ReadOnly,
+    WorkoutsOnly,
+}
+
+impl Scope {
+    pub fn parse(value: &str) -> Option<Scope> {
+        match value {
+            "read_only" => Some(Scope::ReadOnly),
+            "workouts_only" => Some(Scope::WorkoutsOnly),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scope::ReadOnly => "read_only",
+            Scope::WorkoutsOnly => "workouts_only",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    User,
+    Admin,
+}
+
+impl Role {
+    pub fn parse(value: &str) -> Option<Role> {
+        match value {
+            "user" => Some(Role::User),
+            "admin" => Some(Role::Admin),
+            _ => None,
+        }
+    }
+}
+
 pub struct MeExtension {
     pub(crate) user_id: i32,
+    pub(crate) role: Role,
+    /// Restrictions of the api key the caller authenticated with, sessions are never restricted.
+    pub(crate) scopes: Vec<Scope>,
+    /// Admin acting as `user_id` through an impersonation session.
+    pub(crate) impersonator_id: Option<i32>,
+    /// Token of the session the caller authenticated with, `None` for api keys.
+    pub(crate) session_token: Option<String>,
+}
+
+impl MeExtension {
+    pub fn new(user_id: i32, role: Role) -> MeExtension {
+        MeExtension {
+            user_id,
+            role,
+            scopes: vec![],
+            impersonator_id: None,
+            session_token: None,
+        }
+    }
+
+    pub fn has_scope(&self, scope: Scope) -> bool {
+        self.scopes.contains(&scope)
+    }
+
+    pub fn allows(&self, access: Access) -> bool {
+        match access {
+            Access::Read => true,
+            Access::Write => !self.has_scope(Scope::ReadOnly),
+            Access::Admin => self.role == Role::Admin && !self.has_scope(Scope::ReadOnly),
+        }
+    }
 }
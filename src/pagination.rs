@@ -0,0 +1,231 @@
+use chrono::{DateTime, Datelike};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sqlx::postgres::PgRow;
+use sqlx::types::Json;
+use sqlx::Row;
+
+use crate::queryable::FieldKind;
+use crate::sorting::Order;
+
+const DEFAULT_PAGE_SIZE: i32 = 50;
+const MAX_PAGE_SIZE: i32 = 200;
+
+/// Last row of a page, handed out to clients as an opaque page token.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Cursor {
+    pub id: i32,
+    /// Sorted columns of the row, see `QueryBuilder::paginate`.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub values: Map<String, Value>,
+}
+
+impl Cursor {
+    pub fn encode(&self) -> String {
+        base64::encode_config(serde_json::to_vec(self).unwrap(), base64::URL_SAFE_NO_PAD)
+    }
+
+    /// An empty token is the first page.
+    pub fn decode(token: &str) -> Result<Option<Cursor>, &'static str> {
+        if token.is_empty() {
+            return Ok(None);
+        }
+
+        base64::decode_config(token, base64::URL_SAFE_NO_PAD)
+            .ok()
+            .and_then(|cursor| serde_json::from_slice(&cursor).ok())
+            .map(Some)
+            .ok_or("invalid page token")
+    }
+
+    /// Tokens aren't signed, so the values must be the sorted columns, each readable as the
+    /// type of its column, before `QueryBuilder::paginate` hands them to Postgres.
+    pub fn check(&self, orders: &[Order], kinds: &[(&str, FieldKind)]) -> Result<(), &'static str> {
+        if self.values.len() != orders.len() {
+            return Err("invalid page token");
+        }
+
+        for order in orders {
+            let kind = kinds
+                .iter()
+                .find(|(field, _)| *field == order.field)
+                .map(|(_, kind)| *kind);
+
+            let valid = match (kind, self.values.get(&order.field)) {
+                (Some(_), Some(Value::Null)) => true,
+                (Some(FieldKind::Int), Some(Value::Number(value))) => value
+                    .as_i64()
+                    .is_some_and(|value| i32::try_from(value).is_ok()),
+                (Some(FieldKind::Float), Some(Value::Number(_))) => true,
+                (Some(FieldKind::Text), Some(Value::String(_))) => true,
+                // Postgres has no year 0 and refuses it where RFC 3339 allows it
+                (Some(FieldKind::Timestamp), Some(Value::String(value))) => {
+                    DateTime::parse_from_rfc3339(value).is_ok_and(|value| value.year() > 0)
+                }
+                _ => false,
+            };
+
+            if !valid {
+                return Err("invalid page token");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub fn page_size(requested: i32) -> i32 {
+    match requested {
+        size if size <= 0 => DEFAULT_PAGE_SIZE,
+        size => size.min(MAX_PAGE_SIZE),
+    }
+}
+
+/// Cursor of a row selected by a paginated `QueryBuilder`.
+pub fn row_cursor(row: &PgRow) -> Cursor {
+    let Json(values) = row.get::<Json<Map<String, Value>>, _>("page_cursor");
+
+    Cursor {
+        id: row.get("id"),
+        values,
+    }
+}
+
+/// Drops the extra row fetched by `QueryBuilder::paginate` and returns the token of the
+/// following page, empty on the last one.
+pub fn next_page_token<T>(
+    items: &mut Vec<T>,
+    page_size: i32,
+    cursor: impl Fn(&T) -> Cursor,
+) -> String {
+    if items.len() <= page_size as usize {
+        return String::new();
+    }
+
+    items.truncate(page_size as usize);
+
+    items
+        .last()
+        .map(|item| cursor(item).encode())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::sorting::Direction;
+
+    const KINDS: &[(&str, FieldKind)] = &[
+        ("rate", FieldKind::Int),
+        ("weight", FieldKind::Float),
+        ("name", FieldKind::Text),
+        ("day", FieldKind::Timestamp),
+    ];
+
+    fn cursor(values: serde_json::Value) -> Cursor {
+        serde_json::from_value(json!({ "id": 1, "values": values })).unwrap()
+    }
+
+    fn orders(fields: &[&str]) -> Vec<Order> {
+        fields
+            .iter()
+            .map(|field| Order::new(*field, Direction::Asc))
+            .collect()
+    }
+
+    #[test]
+    fn round_trips_cursors_through_tokens() {
+        let token = cursor(json!({ "rate": 5, "day": null })).encode();
+        let decoded = Cursor::decode(&token).unwrap().unwrap();
+
+        assert_eq!(decoded.id, 1);
+        assert_eq!(decoded.values.get("rate"), Some(&json!(5)));
+        assert_eq!(decoded.values.get("day"), Some(&json!(null)));
+        assert!(!token.contains('='));
+    }
+
+    #[test]
+    fn decodes_empty_and_invalid_tokens() {
+        assert!(Cursor::decode("").unwrap().is_none());
+        assert_eq!(
+            Cursor::decode("not a token").unwrap_err(),
+            "invalid page token"
+        );
+        assert_eq!(
+            Cursor::decode(&base64::encode_config("[]", base64::URL_SAFE_NO_PAD)).unwrap_err(),
+            "invalid page token"
+        );
+    }
+
+    #[test]
+    fn accepts_values_of_the_sorted_columns() {
+        let row = cursor(json!({
+            "rate": 5,
+            "weight": 2.5,
+            "name": "squat",
+            "day": "2022-05-30T08:11:27.123456+00:00",
+        }));
+
+        assert!(row
+            .check(&orders(&["rate", "weight", "name", "day"]), KINDS)
+            .is_ok());
+        assert!(cursor(json!({ "day": null }))
+            .check(&orders(&["day"]), KINDS)
+            .is_ok());
+    }
+
+    #[test]
+    fn rejects_values_of_another_sort_or_type() {
+        let invalid = [
+            (json!({ "rate": 5 }), vec!["day"]),
+            (json!({ "rate": 5, "day": null }), vec!["rate"]),
+            (json!({}), vec!["rate"]),
+            (json!({ "rate": "5" }), vec!["rate"]),
+            (json!({ "rate": 1.5 }), vec!["rate"]),
+            (json!({ "rate": 4294967296i64 }), vec!["rate"]),
+            (json!({ "weight": "heavy" }), vec!["weight"]),
+            (json!({ "name": 1 }), vec!["name"]),
+            (json!({ "day": "x" }), vec!["day"]),
+            (json!({ "day": "0000-01-01T00:00:00+00:00" }), vec!["day"]),
+            (json!({ "day": [] }), vec!["day"]),
+            (json!({ "permissions": {} }), vec!["permissions"]),
+        ];
+
+        for (values, fields) in invalid {
+            assert_eq!(
+                cursor(values.clone()).check(&orders(&fields), KINDS),
+                Err("invalid page token"),
+                "{} sorted by {:?}",
+                values,
+                fields
+            );
+        }
+    }
+
+    #[test]
+    fn clamps_page_sizes() {
+        assert_eq!(page_size(0), DEFAULT_PAGE_SIZE);
+        assert_eq!(page_size(-1), DEFAULT_PAGE_SIZE);
+        assert_eq!(page_size(10), 10);
+        assert_eq!(page_size(MAX_PAGE_SIZE + 1), MAX_PAGE_SIZE);
+    }
+
+    #[test]
+    fn hands_out_a_token_only_when_another_page_follows() {
+        let cursor = |id: &i32| Cursor {
+            id: *id,
+            values: Map::new(),
+        };
+
+        let mut items = vec![1, 2];
+        assert_eq!(next_page_token(&mut items, 2, cursor), "");
+        assert_eq!(items, vec![1, 2]);
+
+        let mut items = vec![1, 2, 3];
+        let token = next_page_token(&mut items, 2, cursor);
+        assert_eq!(items, vec![1, 2]);
+        assert_eq!(Cursor::decode(&token).unwrap().unwrap().id, 2);
+    }
+}
@@ -0,0 +1,93 @@
+use crate::me_extension::MeExtension;
+use crate::rpc_method::RpcMethod;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    Read,
+    Write,
+    Admin,
+}
+
+/// Access required by each rpc behind `AuthInterceptor`; methods missing here are refused.
+const POLICY: &[(&str, &str, Access)] = &[
+    ("AdminService", "GetUsers", Access::Admin),
+    ("AdminService", "DisableUser", Access::Admin),
+    ("AdminService", "EnableUser", Access::Admin),
+    ("AdminService", "RevokeUserSessions", Access::Admin),
+    ("AdminService", "GetUserResourceCounts", Access::Admin),
+    ("AdminService", "ImpersonateUser", Access::Admin),
+    ("ApiKeyService", "CreateApiKey", Access::Write),
+    ("ApiKeyService", "DeleteApiKey", Access::Write),
+    ("ApiKeyService", "GetApiKeys", Access::Read),
+    ("AuditService", "GetAuditLog", Access::Read),
+    ("CoachService", "InviteAthlete", Access::Write),
+    ("CoachService", "AcceptCoach", Access::Write),
+    ("CoachService", "RevokeCoachRelationship", Access::Write),
+    ("CoachService", "GetCoachRelationships", Access::Read),
+    ("CoachService", "GetAthletes", Access::Read),
+    ("ExerciseService", "GetExercise", Access::Read),
+    ("ExerciseService", "CreateExercise", Access::Write),
+    ("ExerciseService", "UpdateExercise", Access::Write),
+    ("ExerciseService", "DeleteExercise", Access::Write),
+    ("ExerciseService", "GetExercises", Access::Read),
+    ("ExerciseService", "ShareExercise", Access::Write),
+    ("ExerciseService", "UnshareExercise", Access::Write),
+    ("ExerciseService", "ListExerciseCollaborators", Access::Read),
+    ("GroupService", "CreateGroup", Access::Write),
+    ("GroupService", "DeleteGroup", Access::Write),
+    ("GroupService", "GetGroups", Access::Read),
+    ("GroupService", "GetGroupMembers", Access::Read),
+    ("GroupService", "AddGroupMember", Access::Write),
+    ("GroupService", "UpdateGroupMember", Access::Write),
+    ("GroupService", "RemoveGroupMember", Access::Write),
+    ("SessionService", "DeleteSession", Access::Write),
+    ("SessionService", "GetSessions", Access::Read),
+    ("ShareLinkService", "CreateWorkoutShareLink", Access::Write),
+    ("ShareLinkService", "DeleteWorkoutShareLink", Access::Write),
+    ("ShareLinkService", "GetWorkoutShareLinks", Access::Read),
+    ("UserService", "Me", Access::Read),
+    ("UserService", "ChangePassword", Access::Write),
+    ("UserService", "EnrollTwoFactor", Access::Write),
+    ("UserService", "ConfirmTwoFactor", Access::Write),
+    ("WorkoutService", "GetWorkout", Access::Read),
+    ("WorkoutService", "CreateWorkout", Access::Write),
+    ("WorkoutService", "UpdateWorkout", Access::Write),
+    ("WorkoutService", "DeleteWorkout", Access::Write),
+    ("WorkoutService", "GetWorkouts", Access::Read),
+    ("WorkoutService", "ShareWorkout", Access::Write),
+    ("WorkoutService", "UnshareWorkout", Access::Write),
+    ("WorkoutService", "ListWorkoutCollaborators", Access::Read),
+    ("WorkoutService", "GetWorkoutTree", Access::Read),
+    ("WorkoutRepeatService", "GetWorkoutRepeat", Access::Read),
+    ("WorkoutRepeatService", "CreateWorkoutRepeat", Access::Write),
+    ("WorkoutRepeatService", "UpdateWorkoutRepeat", Access::Write),
+    ("WorkoutRepeatService", "DeleteWorkoutRepeat", Access::Write),
+    ("WorkoutRepeatService", "GetWorkoutRepeats", Access::Read),
+    ("WorkoutSetService", "GetWorkoutSet", Access::Read),
+    ("WorkoutSetService", "CreateWorkoutSet", Access::Write),
+    ("WorkoutSetService", "UpdateWorkoutSet", Access::Write),
+    ("WorkoutSetService", "DeleteWorkoutSet", Access::Write),
+    ("WorkoutSetService", "GetWorkoutSets", Access::Read),
+];
+
+pub fn required_access(rpc_method: &RpcMethod) -> Option<Access> {
+    POLICY
+        .iter()
+        .find(|(service, method, _)| *service == rpc_method.service && *method == rpc_method.method)
+        .map(|(_, _, access)| *access)
+}
+
+pub fn authorize(me: &MeExtension, rpc_method: Option<&RpcMethod>) -> Result<(), &'static str> {
+    let access = rpc_method
+        .and_then(required_access)
+        .ok_or("Unknown rpc method")?;
+
+    if !me.allows(access) {
+        return match access {
+            Access::Admin => Err("Admin role required"),
+            _ => Err("Api key is read-only"),
+        };
+    }
+
+    Ok(())
+}
@@ -1,14 +1,274 @@
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IdQuery {
+    #[prost(oneof = "id_query::Value", tags = "1, 2, 3")]
+    pub value: ::core::option::Option<id_query::Value>,
+}
+/// Nested message and enum types in `IdQuery`.
+pub mod id_query {
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Unknown {}
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Eq {
+        #[prost(int32, tag = "1")]
+        pub value: i32,
+    }
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct In {
+        #[prost(int32, repeated, tag = "1")]
+        pub value: ::prost::alloc::vec::Vec<i32>,
+    }
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Value {
+        #[prost(message, tag = "1")]
+        Unknown(Unknown),
+        #[prost(message, tag = "2")]
+        Eq(Eq),
+        #[prost(message, tag = "3")]
+        In(In),
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Sort {
+    /// one of the sortable fields of the listed message
+    #[prost(string, tag = "1")]
+    pub field: ::prost::alloc::string::String,
+    /// ascending when unspecified
+    #[prost(enumeration = "SortDirection", tag = "2")]
+    pub direction: i32,
+    #[prost(enumeration = "SortNulls", tag = "3")]
+    pub nulls: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IntQuery {
+    #[prost(oneof = "int_query::Value", tags = "1, 2, 3")]
+    pub value: ::core::option::Option<int_query::Value>,
+}
+/// Nested message and enum types in `IntQuery`.
+pub mod int_query {
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Eq {
+        #[prost(int64, tag = "1")]
+        pub value: i64,
+    }
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct In {
+        #[prost(int64, repeated, tag = "1")]
+        pub value: ::prost::alloc::vec::Vec<i64>,
+    }
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Range {
+        #[prost(int64, optional, tag = "1")]
+        pub gt: ::core::option::Option<i64>,
+        #[prost(int64, optional, tag = "2")]
+        pub gte: ::core::option::Option<i64>,
+        #[prost(int64, optional, tag = "3")]
+        pub lt: ::core::option::Option<i64>,
+        #[prost(int64, optional, tag = "4")]
+        pub lte: ::core::option::Option<i64>,
+    }
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Value {
+        #[prost(message, tag = "1")]
+        Eq(Eq),
+        #[prost(message, tag = "2")]
+        In(In),
+        #[prost(message, tag = "3")]
+        Range(Range),
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FloatQuery {
+    #[prost(oneof = "float_query::Value", tags = "1, 2, 3")]
+    pub value: ::core::option::Option<float_query::Value>,
+}
+/// Nested message and enum types in `FloatQuery`.
+pub mod float_query {
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Eq {
+        #[prost(double, tag = "1")]
+        pub value: f64,
+    }
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct In {
+        #[prost(double, repeated, tag = "1")]
+        pub value: ::prost::alloc::vec::Vec<f64>,
+    }
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Range {
+        #[prost(double, optional, tag = "1")]
+        pub gt: ::core::option::Option<f64>,
+        #[prost(double, optional, tag = "2")]
+        pub gte: ::core::option::Option<f64>,
+        #[prost(double, optional, tag = "3")]
+        pub lt: ::core::option::Option<f64>,
+        #[prost(double, optional, tag = "4")]
+        pub lte: ::core::option::Option<f64>,
+    }
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Value {
+        #[prost(message, tag = "1")]
+        Eq(Eq),
+        #[prost(message, tag = "2")]
+        In(In),
+        #[prost(message, tag = "3")]
+        Range(Range),
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StringQuery {
+    #[prost(oneof = "string_query::Value", tags = "1, 2, 3, 4")]
+    pub value: ::core::option::Option<string_query::Value>,
+}
+/// Nested message and enum types in `StringQuery`.
+pub mod string_query {
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Eq {
+        #[prost(string, tag = "1")]
+        pub value: ::prost::alloc::string::String,
+    }
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct In {
+        #[prost(string, repeated, tag = "1")]
+        pub value: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    }
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Value {
+        #[prost(message, tag = "1")]
+        Eq(Eq),
+        #[prost(message, tag = "2")]
+        In(In),
+        /// case-insensitive
+        #[prost(string, tag = "3")]
+        Prefix(::prost::alloc::string::String),
+        /// case-insensitive
+        #[prost(string, tag = "4")]
+        Contains(::prost::alloc::string::String),
+    }
+}
+/// RFC 3339 bounds.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TimestampQuery {
+    #[prost(string, optional, tag = "1")]
+    pub gt: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "2")]
+    pub gte: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "3")]
+    pub lt: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "4")]
+    pub lte: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FieldFilter {
+    /// one of the filterable fields of the listed message
+    #[prost(string, tag = "1")]
+    pub field: ::prost::alloc::string::String,
+    #[prost(oneof = "field_filter::Condition", tags = "2, 3, 4, 5, 6")]
+    pub condition: ::core::option::Option<field_filter::Condition>,
+}
+/// Nested message and enum types in `FieldFilter`.
+pub mod field_filter {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Condition {
+        #[prost(message, tag = "2")]
+        Integer(super::IntQuery),
+        #[prost(message, tag = "3")]
+        Number(super::FloatQuery),
+        #[prost(message, tag = "4")]
+        Text(super::StringQuery),
+        #[prost(message, tag = "5")]
+        Timestamp(super::TimestampQuery),
+        /// true keeps the rows where the field is null, false the others
+        #[prost(bool, tag = "6")]
+        IsNull(bool),
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Filter {
+    #[prost(oneof = "filter::Value", tags = "1, 2, 3, 4")]
+    pub value: ::core::option::Option<filter::Value>,
+}
+/// Nested message and enum types in `Filter`.
+pub mod filter {
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Group {
+        #[prost(message, repeated, tag = "1")]
+        pub filters: ::prost::alloc::vec::Vec<super::Filter>,
+    }
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Value {
+        #[prost(message, tag = "1")]
+        Field(super::FieldFilter),
+        #[prost(message, tag = "2")]
+        And(Group),
+        #[prost(message, tag = "3")]
+        Or(Group),
+        #[prost(message, tag = "4")]
+        Not(::prost::alloc::boxed::Box<super::Filter>),
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum SortDirection {
+    Unspecified = 0,
+    Asc = 1,
+    Desc = 2,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum SortNulls {
+    /// last when ascending, first when descending
+    Unspecified = 0,
+    First = 1,
+    Last = 2,
+}
+/// Either a user or, when `group_id` is set, a group.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Collaborator {
+    #[prost(int32, tag = "1")]
+    pub user_id: i32,
+    #[prost(string, tag = "2")]
+    pub email: ::prost::alloc::string::String,
+    #[prost(enumeration = "PermissionLevel", tag = "3")]
+    pub level: i32,
+    #[prost(int32, tag = "4")]
+    pub group_id: i32,
+    #[prost(string, tag = "5")]
+    pub group_name: ::prost::alloc::string::String,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum PermissionLevel {
+    Unspecified = 0,
+    Viewer = 1,
+    Editor = 2,
+    Owner = 3,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetExerciseRequest {
     #[prost(int32, tag = "1")]
     pub id: i32,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GetExercisesRequest {}
+pub struct GetExercisesRequest {
+    /// defaults to 50, at most 200
+    #[prost(int32, tag = "1")]
+    pub page_size: i32,
+    /// next_page_token of the previous page, empty for the first one
+    #[prost(string, tag = "2")]
+    pub page_token: ::prost::alloc::string::String,
+    /// applied in order, ties are broken by id
+    #[prost(message, repeated, tag = "3")]
+    pub sort: ::prost::alloc::vec::Vec<Sort>,
+    #[prost(message, optional, tag = "4")]
+    pub filter: ::core::option::Option<Filter>,
+}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetExercisesResponse {
     #[prost(message, repeated, tag = "1")]
     pub exercises: ::prost::alloc::vec::Vec<Exercise>,
+    /// empty on the last page
+    #[prost(string, tag = "2")]
+    pub next_page_token: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CreateExerciseRequest {
@@ -34,6 +294,47 @@ pub struct DeleteExerciseRequest {
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct DeleteExerciseResponse {}
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ShareExerciseRequest {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(string, tag = "2")]
+    pub email: ::prost::alloc::string::String,
+    #[prost(enumeration = "PermissionLevel", tag = "3")]
+    pub level: i32,
+    /// shares with a group instead of the user with `email`
+    #[prost(int32, optional, tag = "4")]
+    pub group_id: ::core::option::Option<i32>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ShareExerciseResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub collaborators: ::prost::alloc::vec::Vec<Collaborator>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UnshareExerciseRequest {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(int32, tag = "2")]
+    pub collaborator_id: i32,
+    #[prost(int32, optional, tag = "3")]
+    pub group_id: ::core::option::Option<i32>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UnshareExerciseResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub collaborators: ::prost::alloc::vec::Vec<Collaborator>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListExerciseCollaboratorsRequest {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListExerciseCollaboratorsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub collaborators: ::prost::alloc::vec::Vec<Collaborator>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Exercise {
     #[prost(int32, tag = "1")]
     pub id: i32,
@@ -73,6 +374,18 @@ pub mod exercise_service_server {
             &self,
             request: tonic::Request<super::GetExercisesRequest>,
         ) -> Result<tonic::Response<super::GetExercisesResponse>, tonic::Status>;
+        async fn share_exercise(
+            &self,
+            request: tonic::Request<super::ShareExerciseRequest>,
+        ) -> Result<tonic::Response<super::ShareExerciseResponse>, tonic::Status>;
+        async fn unshare_exercise(
+            &self,
+            request: tonic::Request<super::UnshareExerciseRequest>,
+        ) -> Result<tonic::Response<super::UnshareExerciseResponse>, tonic::Status>;
+        async fn list_exercise_collaborators(
+            &self,
+            request: tonic::Request<super::ListExerciseCollaboratorsRequest>,
+        ) -> Result<tonic::Response<super::ListExerciseCollaboratorsResponse>, tonic::Status>;
     }
     #[derive(Debug)]
     pub struct ExerciseServiceServer<T: ExerciseService> {
@@ -281,6 +594,109 @@ pub mod exercise_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/santa_cruz.ExerciseService/ShareExercise" => {
+                    #[allow(non_camel_case_types)]
+                    struct ShareExerciseSvc<T: ExerciseService>(pub Arc<T>);
+                    impl<T: ExerciseService>
+                        tonic::server::UnaryService<super::ShareExerciseRequest>
+                        for ShareExerciseSvc<T>
+                    {
+                        type Response = super::ShareExerciseResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ShareExerciseRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).share_exercise(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ShareExerciseSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.ExerciseService/UnshareExercise" => {
+                    #[allow(non_camel_case_types)]
+                    struct UnshareExerciseSvc<T: ExerciseService>(pub Arc<T>);
+                    impl<T: ExerciseService>
+                        tonic::server::UnaryService<super::UnshareExerciseRequest>
+                        for UnshareExerciseSvc<T>
+                    {
+                        type Response = super::UnshareExerciseResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UnshareExerciseRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).unshare_exercise(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = UnshareExerciseSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.ExerciseService/ListExerciseCollaborators" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListExerciseCollaboratorsSvc<T: ExerciseService>(pub Arc<T>);
+                    impl<T: ExerciseService>
+                        tonic::server::UnaryService<super::ListExerciseCollaboratorsRequest>
+                        for ListExerciseCollaboratorsSvc<T>
+                    {
+                        type Response = super::ListExerciseCollaboratorsResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListExerciseCollaboratorsRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut =
+                                async move { (*inner).list_exercise_collaborators(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListExerciseCollaboratorsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => Box::pin(async move {
                     Ok(http::Response::builder()
                         .status(200)
@@ -317,104 +733,120 @@ pub mod exercise_service_server {
     }
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GetWorkoutRequest {
+pub struct GetWorkoutRepeatRequest {
     #[prost(int32, tag = "1")]
     pub id: i32,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GetWorkoutsRequest {}
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GetWorkoutsResponse {
-    #[prost(message, repeated, tag = "1")]
-    pub workouts: ::prost::alloc::vec::Vec<Workout>,
+pub struct GetWorkoutRepeatsRequest {
+    #[prost(message, optional, tag = "1")]
+    pub workout_set_id: ::core::option::Option<IdQuery>,
+    /// defaults to 50, at most 200
+    #[prost(int32, tag = "2")]
+    pub page_size: i32,
+    /// next_page_token of the previous page, empty for the first one
+    #[prost(string, tag = "3")]
+    pub page_token: ::prost::alloc::string::String,
+    /// applied in order, ties are broken by id
+    #[prost(message, repeated, tag = "4")]
+    pub sort: ::prost::alloc::vec::Vec<Sort>,
+    #[prost(message, optional, tag = "5")]
+    pub filter: ::core::option::Option<Filter>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct CreateWorkoutRequest {
-    #[prost(enumeration = "WorkoutStatus", tag = "1")]
-    pub status: i32,
+pub struct GetWorkoutRepeatsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub workout_repeats: ::prost::alloc::vec::Vec<WorkoutRepeat>,
+    /// empty on the last page
     #[prost(string, tag = "2")]
-    pub day: ::prost::alloc::string::String,
+    pub next_page_token: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct UpdateWorkoutRequest {
+pub struct CreateWorkoutRepeatRequest {
+    #[prost(int32, tag = "1")]
+    pub workout_set_id: i32,
+    #[prost(int32, tag = "2")]
+    pub exercise_id: i32,
+    #[prost(int32, optional, tag = "3")]
+    pub repeats: ::core::option::Option<i32>,
+    #[prost(double, optional, tag = "4")]
+    pub weight: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "5")]
+    pub time: ::core::option::Option<f64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateWorkoutRepeatRequest {
     #[prost(int32, tag = "1")]
     pub id: i32,
-    #[prost(enumeration = "WorkoutStatus", optional, tag = "2")]
-    pub status: ::core::option::Option<i32>,
-    #[prost(string, optional, tag = "3")]
-    pub day: ::core::option::Option<::prost::alloc::string::String>,
-    #[prost(string, optional, tag = "6")]
-    pub comment: ::core::option::Option<::prost::alloc::string::String>,
-    #[prost(int32, optional, tag = "7")]
-    pub rate: ::core::option::Option<i32>,
+    #[prost(int32, optional, tag = "2")]
+    pub repeats: ::core::option::Option<i32>,
+    #[prost(double, optional, tag = "3")]
+    pub weight: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "4")]
+    pub time: ::core::option::Option<f64>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct DeleteWorkoutRequest {
+pub struct DeleteWorkoutRepeatRequest {
     #[prost(int32, tag = "1")]
     pub id: i32,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct DeleteWorkoutResponse {}
+pub struct DeleteWorkoutRepeatResponse {}
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct Workout {
+pub struct WorkoutRepeat {
     #[prost(int32, tag = "1")]
     pub id: i32,
     #[prost(string, tag = "2")]
     pub created_at: ::prost::alloc::string::String,
     #[prost(string, tag = "3")]
     pub updated_at: ::prost::alloc::string::String,
-    #[prost(enumeration = "WorkoutStatus", tag = "4")]
-    pub status: i32,
-    #[prost(string, tag = "5")]
-    pub day: ::prost::alloc::string::String,
-    #[prost(string, tag = "6")]
-    pub comment: ::prost::alloc::string::String,
-    #[prost(int32, tag = "7")]
-    pub rate: i32,
-}
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
-#[repr(i32)]
-pub enum WorkoutStatus {
-    Unknown = 0,
-    InProgress = 1,
-    Finished = 2,
+    #[prost(int32, tag = "4")]
+    pub workout_set_id: i32,
+    #[prost(int32, tag = "5")]
+    pub exercise_id: i32,
+    #[prost(int32, tag = "6")]
+    pub repeats: i32,
+    #[prost(double, optional, tag = "7")]
+    pub weight: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "8")]
+    pub time: ::core::option::Option<f64>,
 }
 #[doc = r" Generated server implementations."]
-pub mod workout_service_server {
+pub mod workout_repeat_service_server {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
     use tonic::codegen::*;
-    #[doc = "Generated trait containing gRPC methods that should be implemented for use with WorkoutServiceServer."]
+    #[doc = "Generated trait containing gRPC methods that should be implemented for use with WorkoutRepeatServiceServer."]
     #[async_trait]
-    pub trait WorkoutService: Send + Sync + 'static {
-        async fn get_workout(
+    pub trait WorkoutRepeatService: Send + Sync + 'static {
+        async fn get_workout_repeat(
             &self,
-            request: tonic::Request<super::GetWorkoutRequest>,
-        ) -> Result<tonic::Response<super::Workout>, tonic::Status>;
-        async fn create_workout(
+            request: tonic::Request<super::GetWorkoutRepeatRequest>,
+        ) -> Result<tonic::Response<super::WorkoutRepeat>, tonic::Status>;
+        async fn create_workout_repeat(
             &self,
-            request: tonic::Request<super::CreateWorkoutRequest>,
-        ) -> Result<tonic::Response<super::Workout>, tonic::Status>;
-        async fn update_workout(
+            request: tonic::Request<super::CreateWorkoutRepeatRequest>,
+        ) -> Result<tonic::Response<super::WorkoutRepeat>, tonic::Status>;
+        async fn update_workout_repeat(
             &self,
-            request: tonic::Request<super::UpdateWorkoutRequest>,
-        ) -> Result<tonic::Response<super::Workout>, tonic::Status>;
-        async fn delete_workout(
+            request: tonic::Request<super::UpdateWorkoutRepeatRequest>,
+        ) -> Result<tonic::Response<super::WorkoutRepeat>, tonic::Status>;
+        async fn delete_workout_repeat(
             &self,
-            request: tonic::Request<super::DeleteWorkoutRequest>,
-        ) -> Result<tonic::Response<super::DeleteWorkoutResponse>, tonic::Status>;
-        async fn get_workouts(
+            request: tonic::Request<super::DeleteWorkoutRepeatRequest>,
+        ) -> Result<tonic::Response<super::DeleteWorkoutRepeatResponse>, tonic::Status>;
+        async fn get_workout_repeats(
             &self,
-            request: tonic::Request<super::GetWorkoutsRequest>,
-        ) -> Result<tonic::Response<super::GetWorkoutsResponse>, tonic::Status>;
+            request: tonic::Request<super::GetWorkoutRepeatsRequest>,
+        ) -> Result<tonic::Response<super::GetWorkoutRepeatsResponse>, tonic::Status>;
     }
     #[derive(Debug)]
-    pub struct WorkoutServiceServer<T: WorkoutService> {
+    pub struct WorkoutRepeatServiceServer<T: WorkoutRepeatService> {
         inner: _Inner<T>,
         accept_compression_encodings: (),
         send_compression_encodings: (),
     }
     struct _Inner<T>(Arc<T>);
-    impl<T: WorkoutService> WorkoutServiceServer<T> {
+    impl<T: WorkoutRepeatService> WorkoutRepeatServiceServer<T> {
         pub fn new(inner: T) -> Self {
             let inner = Arc::new(inner);
             let inner = _Inner(inner);
@@ -431,9 +863,9 @@ pub mod workout_service_server {
             InterceptedService::new(Self::new(inner), interceptor)
         }
     }
-    impl<T, B> tonic::codegen::Service<http::Request<B>> for WorkoutServiceServer<T>
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for WorkoutRepeatServiceServer<T>
     where
-        T: WorkoutService,
+        T: WorkoutRepeatService,
         B: Body + Send + 'static,
         B::Error: Into<StdError> + Send + 'static,
     {
@@ -446,18 +878,21 @@ pub mod workout_service_server {
         fn call(&mut self, req: http::Request<B>) -> Self::Future {
             let inner = self.inner.clone();
             match req.uri().path() {
-                "/santa_cruz.WorkoutService/GetWorkout" => {
+                "/santa_cruz.WorkoutRepeatService/GetWorkoutRepeat" => {
                     #[allow(non_camel_case_types)]
-                    struct GetWorkoutSvc<T: WorkoutService>(pub Arc<T>);
-                    impl<T: WorkoutService> tonic::server::UnaryService<super::GetWorkoutRequest> for GetWorkoutSvc<T> {
-                        type Response = super::Workout;
+                    struct GetWorkoutRepeatSvc<T: WorkoutRepeatService>(pub Arc<T>);
+                    impl<T: WorkoutRepeatService>
+                        tonic::server::UnaryService<super::GetWorkoutRepeatRequest>
+                        for GetWorkoutRepeatSvc<T>
+                    {
+                        type Response = super::WorkoutRepeat;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::GetWorkoutRequest>,
+                            request: tonic::Request<super::GetWorkoutRepeatRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).get_workout(request).await };
+                            let fut = async move { (*inner).get_workout_repeat(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -466,7 +901,7 @@ pub mod workout_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = GetWorkoutSvc(inner);
+                        let method = GetWorkoutRepeatSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -477,20 +912,21 @@ pub mod workout_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/santa_cruz.WorkoutService/CreateWorkout" => {
+                "/santa_cruz.WorkoutRepeatService/CreateWorkoutRepeat" => {
                     #[allow(non_camel_case_types)]
-                    struct CreateWorkoutSvc<T: WorkoutService>(pub Arc<T>);
-                    impl<T: WorkoutService> tonic::server::UnaryService<super::CreateWorkoutRequest>
-                        for CreateWorkoutSvc<T>
+                    struct CreateWorkoutRepeatSvc<T: WorkoutRepeatService>(pub Arc<T>);
+                    impl<T: WorkoutRepeatService>
+                        tonic::server::UnaryService<super::CreateWorkoutRepeatRequest>
+                        for CreateWorkoutRepeatSvc<T>
                     {
-                        type Response = super::Workout;
+                        type Response = super::WorkoutRepeat;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::CreateWorkoutRequest>,
+                            request: tonic::Request<super::CreateWorkoutRepeatRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).create_workout(request).await };
+                            let fut = async move { (*inner).create_workout_repeat(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -499,7 +935,7 @@ pub mod workout_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = CreateWorkoutSvc(inner);
+                        let method = CreateWorkoutRepeatSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -510,20 +946,21 @@ pub mod workout_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/santa_cruz.WorkoutService/UpdateWorkout" => {
+                "/santa_cruz.WorkoutRepeatService/UpdateWorkoutRepeat" => {
                     #[allow(non_camel_case_types)]
-                    struct UpdateWorkoutSvc<T: WorkoutService>(pub Arc<T>);
-                    impl<T: WorkoutService> tonic::server::UnaryService<super::UpdateWorkoutRequest>
-                        for UpdateWorkoutSvc<T>
+                    struct UpdateWorkoutRepeatSvc<T: WorkoutRepeatService>(pub Arc<T>);
+                    impl<T: WorkoutRepeatService>
+                        tonic::server::UnaryService<super::UpdateWorkoutRepeatRequest>
+                        for UpdateWorkoutRepeatSvc<T>
                     {
-                        type Response = super::Workout;
+                        type Response = super::WorkoutRepeat;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::UpdateWorkoutRequest>,
+                            request: tonic::Request<super::UpdateWorkoutRepeatRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).update_workout(request).await };
+                            let fut = async move { (*inner).update_workout_repeat(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -532,7 +969,7 @@ pub mod workout_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = UpdateWorkoutSvc(inner);
+                        let method = UpdateWorkoutRepeatSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -543,20 +980,21 @@ pub mod workout_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/santa_cruz.WorkoutService/DeleteWorkout" => {
+                "/santa_cruz.WorkoutRepeatService/DeleteWorkoutRepeat" => {
                     #[allow(non_camel_case_types)]
-                    struct DeleteWorkoutSvc<T: WorkoutService>(pub Arc<T>);
-                    impl<T: WorkoutService> tonic::server::UnaryService<super::DeleteWorkoutRequest>
-                        for DeleteWorkoutSvc<T>
+                    struct DeleteWorkoutRepeatSvc<T: WorkoutRepeatService>(pub Arc<T>);
+                    impl<T: WorkoutRepeatService>
+                        tonic::server::UnaryService<super::DeleteWorkoutRepeatRequest>
+                        for DeleteWorkoutRepeatSvc<T>
                     {
-                        type Response = super::DeleteWorkoutResponse;
+                        type Response = super::DeleteWorkoutRepeatResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::DeleteWorkoutRequest>,
+                            request: tonic::Request<super::DeleteWorkoutRepeatRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).delete_workout(request).await };
+                            let fut = async move { (*inner).delete_workout_repeat(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -565,7 +1003,7 @@ pub mod workout_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = DeleteWorkoutSvc(inner);
+                        let method = DeleteWorkoutRepeatSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -576,20 +1014,21 @@ pub mod workout_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/santa_cruz.WorkoutService/GetWorkouts" => {
+                "/santa_cruz.WorkoutRepeatService/GetWorkoutRepeats" => {
                     #[allow(non_camel_case_types)]
-                    struct GetWorkoutsSvc<T: WorkoutService>(pub Arc<T>);
-                    impl<T: WorkoutService> tonic::server::UnaryService<super::GetWorkoutsRequest>
-                        for GetWorkoutsSvc<T>
+                    struct GetWorkoutRepeatsSvc<T: WorkoutRepeatService>(pub Arc<T>);
+                    impl<T: WorkoutRepeatService>
+                        tonic::server::UnaryService<super::GetWorkoutRepeatsRequest>
+                        for GetWorkoutRepeatsSvc<T>
                     {
-                        type Response = super::GetWorkoutsResponse;
+                        type Response = super::GetWorkoutRepeatsResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::GetWorkoutsRequest>,
+                            request: tonic::Request<super::GetWorkoutRepeatsRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).get_workouts(request).await };
+                            let fut = async move { (*inner).get_workout_repeats(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -598,7 +1037,7 @@ pub mod workout_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = GetWorkoutsSvc(inner);
+                        let method = GetWorkoutRepeatsSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -620,7 +1059,7 @@ pub mod workout_service_server {
             }
         }
     }
-    impl<T: WorkoutService> Clone for WorkoutServiceServer<T> {
+    impl<T: WorkoutRepeatService> Clone for WorkoutRepeatServiceServer<T> {
         fn clone(&self) -> Self {
             let inner = self.inner.clone();
             Self {
@@ -630,7 +1069,7 @@ pub mod workout_service_server {
             }
         }
     }
-    impl<T: WorkoutService> Clone for _Inner<T> {
+    impl<T: WorkoutRepeatService> Clone for _Inner<T> {
         fn clone(&self) -> Self {
             Self(self.0.clone())
         }
@@ -640,8 +1079,8 @@ pub mod workout_service_server {
             write!(f, "{:?}", self.0)
         }
     }
-    impl<T: WorkoutService> tonic::transport::NamedService for WorkoutServiceServer<T> {
-        const NAME: &'static str = "santa_cruz.WorkoutService";
+    impl<T: WorkoutRepeatService> tonic::transport::NamedService for WorkoutRepeatServiceServer<T> {
+        const NAME: &'static str = "santa_cruz.WorkoutRepeatService";
     }
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -653,11 +1092,25 @@ pub struct GetWorkoutSetRequest {
 pub struct GetWorkoutSetsRequest {
     #[prost(int32, tag = "1")]
     pub workout_id: i32,
+    /// defaults to 50, at most 200
+    #[prost(int32, tag = "2")]
+    pub page_size: i32,
+    /// next_page_token of the previous page, empty for the first one
+    #[prost(string, tag = "3")]
+    pub page_token: ::prost::alloc::string::String,
+    /// applied in order, ties are broken by id; by position when empty
+    #[prost(message, repeated, tag = "4")]
+    pub sort: ::prost::alloc::vec::Vec<Sort>,
+    #[prost(message, optional, tag = "5")]
+    pub filter: ::core::option::Option<Filter>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetWorkoutSetsResponse {
     #[prost(message, repeated, tag = "1")]
     pub workout_sets: ::prost::alloc::vec::Vec<WorkoutSet>,
+    /// empty on the last page
+    #[prost(string, tag = "2")]
+    pub next_page_token: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CreateWorkoutSetRequest {
@@ -1002,135 +1455,2149 @@ pub mod workout_set_service_server {
     }
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct IdQuery {
-    #[prost(oneof = "id_query::Value", tags = "1, 2, 3")]
-    pub value: ::core::option::Option<id_query::Value>,
-}
-/// Nested message and enum types in `IdQuery`.
-pub mod id_query {
-    #[derive(Clone, PartialEq, ::prost::Message)]
-    pub struct Unknown {}
-    #[derive(Clone, PartialEq, ::prost::Message)]
-    pub struct Eq {
-        #[prost(int32, tag = "1")]
-        pub value: i32,
-    }
-    #[derive(Clone, PartialEq, ::prost::Message)]
-    pub struct In {
-        #[prost(int32, repeated, tag = "1")]
-        pub value: ::prost::alloc::vec::Vec<i32>,
-    }
-    #[derive(Clone, PartialEq, ::prost::Oneof)]
-    pub enum Value {
-        #[prost(message, tag = "1")]
-        Unknown(Unknown),
-        #[prost(message, tag = "2")]
-        Eq(Eq),
-        #[prost(message, tag = "3")]
-        In(In),
-    }
-}
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GetWorkoutRepeatRequest {
+pub struct GetWorkoutRequest {
     #[prost(int32, tag = "1")]
     pub id: i32,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GetWorkoutRepeatsRequest {
-    #[prost(message, optional, tag = "1")]
-    pub workout_set_id: ::core::option::Option<IdQuery>,
+pub struct GetWorkoutsRequest {
+    /// defaults to 50, at most 200
+    #[prost(int32, tag = "1")]
+    pub page_size: i32,
+    /// next_page_token of the previous page, empty for the first one
+    #[prost(string, tag = "2")]
+    pub page_token: ::prost::alloc::string::String,
+    /// applied in order, ties are broken by id
+    #[prost(message, repeated, tag = "3")]
+    pub sort: ::prost::alloc::vec::Vec<Sort>,
+    #[prost(message, optional, tag = "4")]
+    pub filter: ::core::option::Option<Filter>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GetWorkoutRepeatsResponse {
+pub struct GetWorkoutsResponse {
     #[prost(message, repeated, tag = "1")]
-    pub workout_repeats: ::prost::alloc::vec::Vec<WorkoutRepeat>,
+    pub workouts: ::prost::alloc::vec::Vec<Workout>,
+    /// empty on the last page
+    #[prost(string, tag = "2")]
+    pub next_page_token: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct CreateWorkoutRepeatRequest {
-    #[prost(int32, tag = "1")]
-    pub workout_set_id: i32,
-    #[prost(int32, tag = "2")]
-    pub exercise_id: i32,
+pub struct CreateWorkoutRequest {
+    #[prost(enumeration = "WorkoutStatus", tag = "1")]
+    pub status: i32,
+    #[prost(string, tag = "2")]
+    pub day: ::prost::alloc::string::String,
+    /// creates the workout on behalf of an athlete coached by the caller
     #[prost(int32, optional, tag = "3")]
-    pub repeats: ::core::option::Option<i32>,
-    #[prost(double, optional, tag = "4")]
-    pub weight: ::core::option::Option<f64>,
-    #[prost(double, optional, tag = "5")]
-    pub time: ::core::option::Option<f64>,
+    pub athlete_id: ::core::option::Option<i32>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct UpdateWorkoutRepeatRequest {
+pub struct UpdateWorkoutRequest {
     #[prost(int32, tag = "1")]
     pub id: i32,
-    #[prost(int32, optional, tag = "2")]
-    pub repeats: ::core::option::Option<i32>,
-    #[prost(double, optional, tag = "3")]
-    pub weight: ::core::option::Option<f64>,
-    #[prost(double, optional, tag = "4")]
-    pub time: ::core::option::Option<f64>,
+    #[prost(enumeration = "WorkoutStatus", optional, tag = "2")]
+    pub status: ::core::option::Option<i32>,
+    #[prost(string, optional, tag = "3")]
+    pub day: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "6")]
+    pub comment: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(int32, optional, tag = "7")]
+    pub rate: ::core::option::Option<i32>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct DeleteWorkoutRepeatRequest {
+pub struct DeleteWorkoutRequest {
     #[prost(int32, tag = "1")]
     pub id: i32,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct DeleteWorkoutRepeatResponse {}
+pub struct DeleteWorkoutResponse {}
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct WorkoutRepeat {
+pub struct ShareWorkoutRequest {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(string, tag = "2")]
+    pub email: ::prost::alloc::string::String,
+    #[prost(enumeration = "PermissionLevel", tag = "3")]
+    pub level: i32,
+    /// shares with a group instead of the user with `email`
+    #[prost(int32, optional, tag = "4")]
+    pub group_id: ::core::option::Option<i32>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ShareWorkoutResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub collaborators: ::prost::alloc::vec::Vec<Collaborator>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UnshareWorkoutRequest {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(int32, tag = "2")]
+    pub collaborator_id: i32,
+    #[prost(int32, optional, tag = "3")]
+    pub group_id: ::core::option::Option<i32>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UnshareWorkoutResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub collaborators: ::prost::alloc::vec::Vec<Collaborator>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListWorkoutCollaboratorsRequest {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListWorkoutCollaboratorsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub collaborators: ::prost::alloc::vec::Vec<Collaborator>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Workout {
     #[prost(int32, tag = "1")]
     pub id: i32,
     #[prost(string, tag = "2")]
     pub created_at: ::prost::alloc::string::String,
     #[prost(string, tag = "3")]
     pub updated_at: ::prost::alloc::string::String,
-    #[prost(int32, tag = "4")]
-    pub workout_set_id: i32,
-    #[prost(int32, tag = "5")]
-    pub exercise_id: i32,
-    #[prost(int32, tag = "6")]
-    pub repeats: i32,
-    #[prost(double, optional, tag = "7")]
-    pub weight: ::core::option::Option<f64>,
-    #[prost(double, optional, tag = "8")]
-    pub time: ::core::option::Option<f64>,
+    #[prost(enumeration = "WorkoutStatus", tag = "4")]
+    pub status: i32,
+    #[prost(string, tag = "5")]
+    pub day: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub comment: ::prost::alloc::string::String,
+    #[prost(int32, tag = "7")]
+    pub rate: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetWorkoutTreeRequest {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WorkoutTreeSet {
+    #[prost(message, optional, tag = "1")]
+    pub workout_set: ::core::option::Option<WorkoutSet>,
+    #[prost(message, repeated, tag = "2")]
+    pub workout_repeats: ::prost::alloc::vec::Vec<WorkoutRepeat>,
+}
+/// A workout with its sets ordered by position, their repeats and the exercises they reference,
+/// limited to what the caller can view.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WorkoutTree {
+    #[prost(message, optional, tag = "1")]
+    pub workout: ::core::option::Option<Workout>,
+    #[prost(message, repeated, tag = "2")]
+    pub workout_sets: ::prost::alloc::vec::Vec<WorkoutTreeSet>,
+    #[prost(message, repeated, tag = "3")]
+    pub exercises: ::prost::alloc::vec::Vec<Exercise>,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum WorkoutStatus {
+    Unknown = 0,
+    InProgress = 1,
+    Finished = 2,
+}
+#[doc = r" Generated server implementations."]
+pub mod workout_service_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    #[doc = "Generated trait containing gRPC methods that should be implemented for use with WorkoutServiceServer."]
+    #[async_trait]
+    pub trait WorkoutService: Send + Sync + 'static {
+        async fn get_workout(
+            &self,
+            request: tonic::Request<super::GetWorkoutRequest>,
+        ) -> Result<tonic::Response<super::Workout>, tonic::Status>;
+        async fn create_workout(
+            &self,
+            request: tonic::Request<super::CreateWorkoutRequest>,
+        ) -> Result<tonic::Response<super::Workout>, tonic::Status>;
+        async fn update_workout(
+            &self,
+            request: tonic::Request<super::UpdateWorkoutRequest>,
+        ) -> Result<tonic::Response<super::Workout>, tonic::Status>;
+        async fn delete_workout(
+            &self,
+            request: tonic::Request<super::DeleteWorkoutRequest>,
+        ) -> Result<tonic::Response<super::DeleteWorkoutResponse>, tonic::Status>;
+        async fn get_workouts(
+            &self,
+            request: tonic::Request<super::GetWorkoutsRequest>,
+        ) -> Result<tonic::Response<super::GetWorkoutsResponse>, tonic::Status>;
+        async fn share_workout(
+            &self,
+            request: tonic::Request<super::ShareWorkoutRequest>,
+        ) -> Result<tonic::Response<super::ShareWorkoutResponse>, tonic::Status>;
+        async fn unshare_workout(
+            &self,
+            request: tonic::Request<super::UnshareWorkoutRequest>,
+        ) -> Result<tonic::Response<super::UnshareWorkoutResponse>, tonic::Status>;
+        async fn list_workout_collaborators(
+            &self,
+            request: tonic::Request<super::ListWorkoutCollaboratorsRequest>,
+        ) -> Result<tonic::Response<super::ListWorkoutCollaboratorsResponse>, tonic::Status>;
+        async fn get_workout_tree(
+            &self,
+            request: tonic::Request<super::GetWorkoutTreeRequest>,
+        ) -> Result<tonic::Response<super::WorkoutTree>, tonic::Status>;
+    }
+    #[derive(Debug)]
+    pub struct WorkoutServiceServer<T: WorkoutService> {
+        inner: _Inner<T>,
+        accept_compression_encodings: (),
+        send_compression_encodings: (),
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: WorkoutService> WorkoutServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            let inner = Arc::new(inner);
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+            }
+        }
+        pub fn with_interceptor<F>(inner: T, interceptor: F) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for WorkoutServiceServer<T>
+    where
+        T: WorkoutService,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = Never;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/santa_cruz.WorkoutService/GetWorkout" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetWorkoutSvc<T: WorkoutService>(pub Arc<T>);
+                    impl<T: WorkoutService> tonic::server::UnaryService<super::GetWorkoutRequest> for GetWorkoutSvc<T> {
+                        type Response = super::Workout;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetWorkoutRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_workout(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetWorkoutSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.WorkoutService/CreateWorkout" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateWorkoutSvc<T: WorkoutService>(pub Arc<T>);
+                    impl<T: WorkoutService> tonic::server::UnaryService<super::CreateWorkoutRequest>
+                        for CreateWorkoutSvc<T>
+                    {
+                        type Response = super::Workout;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreateWorkoutRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).create_workout(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CreateWorkoutSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.WorkoutService/UpdateWorkout" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdateWorkoutSvc<T: WorkoutService>(pub Arc<T>);
+                    impl<T: WorkoutService> tonic::server::UnaryService<super::UpdateWorkoutRequest>
+                        for UpdateWorkoutSvc<T>
+                    {
+                        type Response = super::Workout;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateWorkoutRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).update_workout(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = UpdateWorkoutSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.WorkoutService/DeleteWorkout" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteWorkoutSvc<T: WorkoutService>(pub Arc<T>);
+                    impl<T: WorkoutService> tonic::server::UnaryService<super::DeleteWorkoutRequest>
+                        for DeleteWorkoutSvc<T>
+                    {
+                        type Response = super::DeleteWorkoutResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeleteWorkoutRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).delete_workout(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DeleteWorkoutSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.WorkoutService/GetWorkouts" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetWorkoutsSvc<T: WorkoutService>(pub Arc<T>);
+                    impl<T: WorkoutService> tonic::server::UnaryService<super::GetWorkoutsRequest>
+                        for GetWorkoutsSvc<T>
+                    {
+                        type Response = super::GetWorkoutsResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetWorkoutsRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_workouts(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetWorkoutsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.WorkoutService/ShareWorkout" => {
+                    #[allow(non_camel_case_types)]
+                    struct ShareWorkoutSvc<T: WorkoutService>(pub Arc<T>);
+                    impl<T: WorkoutService> tonic::server::UnaryService<super::ShareWorkoutRequest>
+                        for ShareWorkoutSvc<T>
+                    {
+                        type Response = super::ShareWorkoutResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ShareWorkoutRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).share_workout(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ShareWorkoutSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.WorkoutService/UnshareWorkout" => {
+                    #[allow(non_camel_case_types)]
+                    struct UnshareWorkoutSvc<T: WorkoutService>(pub Arc<T>);
+                    impl<T: WorkoutService>
+                        tonic::server::UnaryService<super::UnshareWorkoutRequest>
+                        for UnshareWorkoutSvc<T>
+                    {
+                        type Response = super::UnshareWorkoutResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UnshareWorkoutRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).unshare_workout(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = UnshareWorkoutSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.WorkoutService/ListWorkoutCollaborators" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListWorkoutCollaboratorsSvc<T: WorkoutService>(pub Arc<T>);
+                    impl<T: WorkoutService>
+                        tonic::server::UnaryService<super::ListWorkoutCollaboratorsRequest>
+                        for ListWorkoutCollaboratorsSvc<T>
+                    {
+                        type Response = super::ListWorkoutCollaboratorsResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListWorkoutCollaboratorsRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut =
+                                async move { (*inner).list_workout_collaborators(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListWorkoutCollaboratorsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.WorkoutService/GetWorkoutTree" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetWorkoutTreeSvc<T: WorkoutService>(pub Arc<T>);
+                    impl<T: WorkoutService>
+                        tonic::server::UnaryService<super::GetWorkoutTreeRequest>
+                        for GetWorkoutTreeSvc<T>
+                    {
+                        type Response = super::WorkoutTree;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetWorkoutTreeRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_workout_tree(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetWorkoutTreeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => Box::pin(async move {
+                    Ok(http::Response::builder()
+                        .status(200)
+                        .header("grpc-status", "12")
+                        .header("content-type", "application/grpc")
+                        .body(empty_body())
+                        .unwrap())
+                }),
+            }
+        }
+    }
+    impl<T: WorkoutService> Clone for WorkoutServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+            }
+        }
+    }
+    impl<T: WorkoutService> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+    impl<T: WorkoutService> tonic::transport::NamedService for WorkoutServiceServer<T> {
+        const NAME: &'static str = "santa_cruz.WorkoutService";
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetSessionsRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetSessionsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub sessions: ::prost::alloc::vec::Vec<Session>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteSessionRequest {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteSessionResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Session {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(string, tag = "2")]
+    pub created_at: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub updated_at: ::prost::alloc::string::String,
+    #[prost(int32, tag = "4")]
+    pub user_id: i32,
+    #[prost(string, tag = "5")]
+    pub device_name: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub expires_at: ::prost::alloc::string::String,
+    /// admin acting as the user through this session, 0 otherwise
+    #[prost(int32, tag = "7")]
+    pub impersonator_id: i32,
+}
+#[doc = r" Generated server implementations."]
+pub mod session_service_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    #[doc = "Generated trait containing gRPC methods that should be implemented for use with SessionServiceServer."]
+    #[async_trait]
+    pub trait SessionService: Send + Sync + 'static {
+        async fn delete_session(
+            &self,
+            request: tonic::Request<super::DeleteSessionRequest>,
+        ) -> Result<tonic::Response<super::DeleteSessionResponse>, tonic::Status>;
+        async fn get_sessions(
+            &self,
+            request: tonic::Request<super::GetSessionsRequest>,
+        ) -> Result<tonic::Response<super::GetSessionsResponse>, tonic::Status>;
+    }
+    #[derive(Debug)]
+    pub struct SessionServiceServer<T: SessionService> {
+        inner: _Inner<T>,
+        accept_compression_encodings: (),
+        send_compression_encodings: (),
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: SessionService> SessionServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            let inner = Arc::new(inner);
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+            }
+        }
+        pub fn with_interceptor<F>(inner: T, interceptor: F) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for SessionServiceServer<T>
+    where
+        T: SessionService,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = Never;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/santa_cruz.SessionService/DeleteSession" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteSessionSvc<T: SessionService>(pub Arc<T>);
+                    impl<T: SessionService> tonic::server::UnaryService<super::DeleteSessionRequest>
+                        for DeleteSessionSvc<T>
+                    {
+                        type Response = super::DeleteSessionResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeleteSessionRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).delete_session(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DeleteSessionSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.SessionService/GetSessions" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetSessionsSvc<T: SessionService>(pub Arc<T>);
+                    impl<T: SessionService> tonic::server::UnaryService<super::GetSessionsRequest>
+                        for GetSessionsSvc<T>
+                    {
+                        type Response = super::GetSessionsResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetSessionsRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_sessions(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetSessionsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => Box::pin(async move {
+                    Ok(http::Response::builder()
+                        .status(200)
+                        .header("grpc-status", "12")
+                        .header("content-type", "application/grpc")
+                        .body(empty_body())
+                        .unwrap())
+                }),
+            }
+        }
+    }
+    impl<T: SessionService> Clone for SessionServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+            }
+        }
+    }
+    impl<T: SessionService> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+    impl<T: SessionService> tonic::transport::NamedService for SessionServiceServer<T> {
+        const NAME: &'static str = "santa_cruz.SessionService";
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MeRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct User {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(string, tag = "2")]
+    pub created_at: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub updated_at: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub email: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChangePasswordRequest {
+    #[prost(string, tag = "1")]
+    pub current_password: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub new_password: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChangePasswordResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EnrollTwoFactorRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EnrollTwoFactorResponse {
+    #[prost(string, tag = "1")]
+    pub secret: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub provisioning_uri: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ConfirmTwoFactorRequest {
+    #[prost(string, tag = "1")]
+    pub code: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ConfirmTwoFactorResponse {
+    #[prost(string, repeated, tag = "1")]
+    pub recovery_codes: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[doc = r" Generated server implementations."]
+pub mod user_service_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    #[doc = "Generated trait containing gRPC methods that should be implemented for use with UserServiceServer."]
+    #[async_trait]
+    pub trait UserService: Send + Sync + 'static {
+        async fn me(
+            &self,
+            request: tonic::Request<super::MeRequest>,
+        ) -> Result<tonic::Response<super::User>, tonic::Status>;
+        async fn change_password(
+            &self,
+            request: tonic::Request<super::ChangePasswordRequest>,
+        ) -> Result<tonic::Response<super::ChangePasswordResponse>, tonic::Status>;
+        async fn enroll_two_factor(
+            &self,
+            request: tonic::Request<super::EnrollTwoFactorRequest>,
+        ) -> Result<tonic::Response<super::EnrollTwoFactorResponse>, tonic::Status>;
+        async fn confirm_two_factor(
+            &self,
+            request: tonic::Request<super::ConfirmTwoFactorRequest>,
+        ) -> Result<tonic::Response<super::ConfirmTwoFactorResponse>, tonic::Status>;
+    }
+    #[derive(Debug)]
+    pub struct UserServiceServer<T: UserService> {
+        inner: _Inner<T>,
+        accept_compression_encodings: (),
+        send_compression_encodings: (),
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: UserService> UserServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            let inner = Arc::new(inner);
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+            }
+        }
+        pub fn with_interceptor<F>(inner: T, interceptor: F) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for UserServiceServer<T>
+    where
+        T: UserService,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = Never;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/santa_cruz.UserService/Me" => {
+                    #[allow(non_camel_case_types)]
+                    struct MeSvc<T: UserService>(pub Arc<T>);
+                    impl<T: UserService> tonic::server::UnaryService<super::MeRequest> for MeSvc<T> {
+                        type Response = super::User;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::MeRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).me(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = MeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.UserService/ChangePassword" => {
+                    #[allow(non_camel_case_types)]
+                    struct ChangePasswordSvc<T: UserService>(pub Arc<T>);
+                    impl<T: UserService> tonic::server::UnaryService<super::ChangePasswordRequest>
+                        for ChangePasswordSvc<T>
+                    {
+                        type Response = super::ChangePasswordResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ChangePasswordRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).change_password(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ChangePasswordSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.UserService/EnrollTwoFactor" => {
+                    #[allow(non_camel_case_types)]
+                    struct EnrollTwoFactorSvc<T: UserService>(pub Arc<T>);
+                    impl<T: UserService> tonic::server::UnaryService<super::EnrollTwoFactorRequest>
+                        for EnrollTwoFactorSvc<T>
+                    {
+                        type Response = super::EnrollTwoFactorResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::EnrollTwoFactorRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).enroll_two_factor(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = EnrollTwoFactorSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.UserService/ConfirmTwoFactor" => {
+                    #[allow(non_camel_case_types)]
+                    struct ConfirmTwoFactorSvc<T: UserService>(pub Arc<T>);
+                    impl<T: UserService> tonic::server::UnaryService<super::ConfirmTwoFactorRequest>
+                        for ConfirmTwoFactorSvc<T>
+                    {
+                        type Response = super::ConfirmTwoFactorResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ConfirmTwoFactorRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).confirm_two_factor(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ConfirmTwoFactorSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => Box::pin(async move {
+                    Ok(http::Response::builder()
+                        .status(200)
+                        .header("grpc-status", "12")
+                        .header("content-type", "application/grpc")
+                        .body(empty_body())
+                        .unwrap())
+                }),
+            }
+        }
+    }
+    impl<T: UserService> Clone for UserServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+            }
+        }
+    }
+    impl<T: UserService> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+    impl<T: UserService> tonic::transport::NamedService for UserServiceServer<T> {
+        const NAME: &'static str = "santa_cruz.UserService";
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RegisterRequest {
+    #[prost(string, tag = "1")]
+    pub email: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub password: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub device_name: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LoginRequest {
+    #[prost(string, tag = "1")]
+    pub email: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub password: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub device_name: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RefreshSessionRequest {
+    #[prost(string, tag = "1")]
+    pub refresh_token: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AuthResponse {
+    #[prost(message, optional, tag = "1")]
+    pub user: ::core::option::Option<User>,
+    #[prost(string, tag = "2")]
+    pub token: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub expires_at: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub refresh_token: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub refresh_expires_at: ::prost::alloc::string::String,
+    #[prost(bool, tag = "6")]
+    pub two_factor_required: bool,
+    #[prost(string, tag = "7")]
+    pub challenge_token: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VerifyTwoFactorRequest {
+    #[prost(string, tag = "1")]
+    pub challenge_token: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub code: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LogoutRequest {
+    #[prost(int32, tag = "1")]
+    pub user_id: i32,
+    #[prost(string, tag = "2")]
+    pub token: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LogoutResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RequestPasswordResetRequest {
+    #[prost(string, tag = "1")]
+    pub email: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RequestPasswordResetResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResetPasswordRequest {
+    #[prost(string, tag = "1")]
+    pub token: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub password: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResetPasswordResponse {}
+#[doc = r" Generated server implementations."]
+pub mod auth_service_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    #[doc = "Generated trait containing gRPC methods that should be implemented for use with AuthServiceServer."]
+    #[async_trait]
+    pub trait AuthService: Send + Sync + 'static {
+        async fn register(
+            &self,
+            request: tonic::Request<super::RegisterRequest>,
+        ) -> Result<tonic::Response<super::AuthResponse>, tonic::Status>;
+        async fn login(
+            &self,
+            request: tonic::Request<super::LoginRequest>,
+        ) -> Result<tonic::Response<super::AuthResponse>, tonic::Status>;
+        async fn logout(
+            &self,
+            request: tonic::Request<super::LogoutRequest>,
+        ) -> Result<tonic::Response<super::LogoutResponse>, tonic::Status>;
+        async fn refresh_session(
+            &self,
+            request: tonic::Request<super::RefreshSessionRequest>,
+        ) -> Result<tonic::Response<super::AuthResponse>, tonic::Status>;
+        async fn verify_two_factor(
+            &self,
+            request: tonic::Request<super::VerifyTwoFactorRequest>,
+        ) -> Result<tonic::Response<super::AuthResponse>, tonic::Status>;
+        async fn request_password_reset(
+            &self,
+            request: tonic::Request<super::RequestPasswordResetRequest>,
+        ) -> Result<tonic::Response<super::RequestPasswordResetResponse>, tonic::Status>;
+        async fn reset_password(
+            &self,
+            request: tonic::Request<super::ResetPasswordRequest>,
+        ) -> Result<tonic::Response<super::ResetPasswordResponse>, tonic::Status>;
+    }
+    #[derive(Debug)]
+    pub struct AuthServiceServer<T: AuthService> {
+        inner: _Inner<T>,
+        accept_compression_encodings: (),
+        send_compression_encodings: (),
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: AuthService> AuthServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            let inner = Arc::new(inner);
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+            }
+        }
+        pub fn with_interceptor<F>(inner: T, interceptor: F) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for AuthServiceServer<T>
+    where
+        T: AuthService,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = Never;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/santa_cruz.AuthService/Register" => {
+                    #[allow(non_camel_case_types)]
+                    struct RegisterSvc<T: AuthService>(pub Arc<T>);
+                    impl<T: AuthService> tonic::server::UnaryService<super::RegisterRequest> for RegisterSvc<T> {
+                        type Response = super::AuthResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RegisterRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).register(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = RegisterSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.AuthService/Login" => {
+                    #[allow(non_camel_case_types)]
+                    struct LoginSvc<T: AuthService>(pub Arc<T>);
+                    impl<T: AuthService> tonic::server::UnaryService<super::LoginRequest> for LoginSvc<T> {
+                        type Response = super::AuthResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::LoginRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).login(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = LoginSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.AuthService/Logout" => {
+                    #[allow(non_camel_case_types)]
+                    struct LogoutSvc<T: AuthService>(pub Arc<T>);
+                    impl<T: AuthService> tonic::server::UnaryService<super::LogoutRequest> for LogoutSvc<T> {
+                        type Response = super::LogoutResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::LogoutRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).logout(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = LogoutSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.AuthService/RefreshSession" => {
+                    #[allow(non_camel_case_types)]
+                    struct RefreshSessionSvc<T: AuthService>(pub Arc<T>);
+                    impl<T: AuthService> tonic::server::UnaryService<super::RefreshSessionRequest>
+                        for RefreshSessionSvc<T>
+                    {
+                        type Response = super::AuthResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RefreshSessionRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).refresh_session(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = RefreshSessionSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.AuthService/VerifyTwoFactor" => {
+                    #[allow(non_camel_case_types)]
+                    struct VerifyTwoFactorSvc<T: AuthService>(pub Arc<T>);
+                    impl<T: AuthService> tonic::server::UnaryService<super::VerifyTwoFactorRequest>
+                        for VerifyTwoFactorSvc<T>
+                    {
+                        type Response = super::AuthResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::VerifyTwoFactorRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).verify_two_factor(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = VerifyTwoFactorSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.AuthService/RequestPasswordReset" => {
+                    #[allow(non_camel_case_types)]
+                    struct RequestPasswordResetSvc<T: AuthService>(pub Arc<T>);
+                    impl<T: AuthService>
+                        tonic::server::UnaryService<super::RequestPasswordResetRequest>
+                        for RequestPasswordResetSvc<T>
+                    {
+                        type Response = super::RequestPasswordResetResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RequestPasswordResetRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).request_password_reset(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = RequestPasswordResetSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.AuthService/ResetPassword" => {
+                    #[allow(non_camel_case_types)]
+                    struct ResetPasswordSvc<T: AuthService>(pub Arc<T>);
+                    impl<T: AuthService> tonic::server::UnaryService<super::ResetPasswordRequest>
+                        for ResetPasswordSvc<T>
+                    {
+                        type Response = super::ResetPasswordResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ResetPasswordRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).reset_password(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ResetPasswordSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => Box::pin(async move {
+                    Ok(http::Response::builder()
+                        .status(200)
+                        .header("grpc-status", "12")
+                        .header("content-type", "application/grpc")
+                        .body(empty_body())
+                        .unwrap())
+                }),
+            }
+        }
+    }
+    impl<T: AuthService> Clone for AuthServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+            }
+        }
+    }
+    impl<T: AuthService> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+    impl<T: AuthService> tonic::transport::NamedService for AuthServiceServer<T> {
+        const NAME: &'static str = "santa_cruz.AuthService";
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateApiKeyRequest {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(enumeration = "ApiKeyScope", repeated, tag = "2")]
+    pub scopes: ::prost::alloc::vec::Vec<i32>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateApiKeyResponse {
+    #[prost(message, optional, tag = "1")]
+    pub api_key: ::core::option::Option<ApiKey>,
+    #[prost(string, tag = "2")]
+    pub key: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteApiKeyRequest {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteApiKeyResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetApiKeysRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetApiKeysResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub api_keys: ::prost::alloc::vec::Vec<ApiKey>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ApiKey {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(string, tag = "2")]
+    pub created_at: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub updated_at: ::prost::alloc::string::String,
+    #[prost(int32, tag = "4")]
+    pub user_id: i32,
+    #[prost(string, tag = "5")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub prefix: ::prost::alloc::string::String,
+    #[prost(enumeration = "ApiKeyScope", repeated, tag = "7")]
+    pub scopes: ::prost::alloc::vec::Vec<i32>,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ApiKeyScope {
+    Unspecified = 0,
+    ReadOnly = 1,
+    WorkoutsOnly = 2,
+}
+#[doc = r" Generated server implementations."]
+pub mod api_key_service_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    #[doc = "Generated trait containing gRPC methods that should be implemented for use with ApiKeyServiceServer."]
+    #[async_trait]
+    pub trait ApiKeyService: Send + Sync + 'static {
+        async fn create_api_key(
+            &self,
+            request: tonic::Request<super::CreateApiKeyRequest>,
+        ) -> Result<tonic::Response<super::CreateApiKeyResponse>, tonic::Status>;
+        async fn delete_api_key(
+            &self,
+            request: tonic::Request<super::DeleteApiKeyRequest>,
+        ) -> Result<tonic::Response<super::DeleteApiKeyResponse>, tonic::Status>;
+        async fn get_api_keys(
+            &self,
+            request: tonic::Request<super::GetApiKeysRequest>,
+        ) -> Result<tonic::Response<super::GetApiKeysResponse>, tonic::Status>;
+    }
+    #[derive(Debug)]
+    pub struct ApiKeyServiceServer<T: ApiKeyService> {
+        inner: _Inner<T>,
+        accept_compression_encodings: (),
+        send_compression_encodings: (),
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: ApiKeyService> ApiKeyServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            let inner = Arc::new(inner);
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+            }
+        }
+        pub fn with_interceptor<F>(inner: T, interceptor: F) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for ApiKeyServiceServer<T>
+    where
+        T: ApiKeyService,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = Never;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/santa_cruz.ApiKeyService/CreateApiKey" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateApiKeySvc<T: ApiKeyService>(pub Arc<T>);
+                    impl<T: ApiKeyService> tonic::server::UnaryService<super::CreateApiKeyRequest>
+                        for CreateApiKeySvc<T>
+                    {
+                        type Response = super::CreateApiKeyResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreateApiKeyRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).create_api_key(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CreateApiKeySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.ApiKeyService/DeleteApiKey" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteApiKeySvc<T: ApiKeyService>(pub Arc<T>);
+                    impl<T: ApiKeyService> tonic::server::UnaryService<super::DeleteApiKeyRequest>
+                        for DeleteApiKeySvc<T>
+                    {
+                        type Response = super::DeleteApiKeyResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeleteApiKeyRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).delete_api_key(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DeleteApiKeySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.ApiKeyService/GetApiKeys" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetApiKeysSvc<T: ApiKeyService>(pub Arc<T>);
+                    impl<T: ApiKeyService> tonic::server::UnaryService<super::GetApiKeysRequest> for GetApiKeysSvc<T> {
+                        type Response = super::GetApiKeysResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetApiKeysRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_api_keys(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetApiKeysSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => Box::pin(async move {
+                    Ok(http::Response::builder()
+                        .status(200)
+                        .header("grpc-status", "12")
+                        .header("content-type", "application/grpc")
+                        .body(empty_body())
+                        .unwrap())
+                }),
+            }
+        }
+    }
+    impl<T: ApiKeyService> Clone for ApiKeyServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+            }
+        }
+    }
+    impl<T: ApiKeyService> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+    impl<T: ApiKeyService> tonic::transport::NamedService for ApiKeyServiceServer<T> {
+        const NAME: &'static str = "santa_cruz.ApiKeyService";
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateWorkoutShareLinkRequest {
+    #[prost(int32, tag = "1")]
+    pub workout_id: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteWorkoutShareLinkRequest {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteWorkoutShareLinkResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetWorkoutShareLinksRequest {
+    #[prost(int32, tag = "1")]
+    pub workout_id: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetWorkoutShareLinksResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub share_links: ::prost::alloc::vec::Vec<WorkoutShareLink>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WorkoutShareLink {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(string, tag = "2")]
+    pub created_at: ::prost::alloc::string::String,
+    #[prost(int32, tag = "3")]
+    pub workout_id: i32,
+    #[prost(string, tag = "4")]
+    pub slug: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetSharedWorkoutRequest {
+    #[prost(string, tag = "1")]
+    pub slug: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SharedWorkoutSet {
+    #[prost(message, optional, tag = "1")]
+    pub workout_set: ::core::option::Option<WorkoutSet>,
+    #[prost(message, repeated, tag = "2")]
+    pub workout_repeats: ::prost::alloc::vec::Vec<WorkoutRepeat>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SharedWorkout {
+    #[prost(message, optional, tag = "1")]
+    pub workout: ::core::option::Option<Workout>,
+    #[prost(message, repeated, tag = "2")]
+    pub workout_sets: ::prost::alloc::vec::Vec<SharedWorkoutSet>,
+}
+#[doc = r" Generated server implementations."]
+pub mod share_link_service_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    #[doc = "Generated trait containing gRPC methods that should be implemented for use with ShareLinkServiceServer."]
+    #[async_trait]
+    pub trait ShareLinkService: Send + Sync + 'static {
+        async fn create_workout_share_link(
+            &self,
+            request: tonic::Request<super::CreateWorkoutShareLinkRequest>,
+        ) -> Result<tonic::Response<super::WorkoutShareLink>, tonic::Status>;
+        async fn delete_workout_share_link(
+            &self,
+            request: tonic::Request<super::DeleteWorkoutShareLinkRequest>,
+        ) -> Result<tonic::Response<super::DeleteWorkoutShareLinkResponse>, tonic::Status>;
+        async fn get_workout_share_links(
+            &self,
+            request: tonic::Request<super::GetWorkoutShareLinksRequest>,
+        ) -> Result<tonic::Response<super::GetWorkoutShareLinksResponse>, tonic::Status>;
+    }
+    #[derive(Debug)]
+    pub struct ShareLinkServiceServer<T: ShareLinkService> {
+        inner: _Inner<T>,
+        accept_compression_encodings: (),
+        send_compression_encodings: (),
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: ShareLinkService> ShareLinkServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            let inner = Arc::new(inner);
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+            }
+        }
+        pub fn with_interceptor<F>(inner: T, interceptor: F) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for ShareLinkServiceServer<T>
+    where
+        T: ShareLinkService,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = Never;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/santa_cruz.ShareLinkService/CreateWorkoutShareLink" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateWorkoutShareLinkSvc<T: ShareLinkService>(pub Arc<T>);
+                    impl<T: ShareLinkService>
+                        tonic::server::UnaryService<super::CreateWorkoutShareLinkRequest>
+                        for CreateWorkoutShareLinkSvc<T>
+                    {
+                        type Response = super::WorkoutShareLink;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreateWorkoutShareLinkRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut =
+                                async move { (*inner).create_workout_share_link(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CreateWorkoutShareLinkSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.ShareLinkService/DeleteWorkoutShareLink" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteWorkoutShareLinkSvc<T: ShareLinkService>(pub Arc<T>);
+                    impl<T: ShareLinkService>
+                        tonic::server::UnaryService<super::DeleteWorkoutShareLinkRequest>
+                        for DeleteWorkoutShareLinkSvc<T>
+                    {
+                        type Response = super::DeleteWorkoutShareLinkResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeleteWorkoutShareLinkRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut =
+                                async move { (*inner).delete_workout_share_link(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DeleteWorkoutShareLinkSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.ShareLinkService/GetWorkoutShareLinks" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetWorkoutShareLinksSvc<T: ShareLinkService>(pub Arc<T>);
+                    impl<T: ShareLinkService>
+                        tonic::server::UnaryService<super::GetWorkoutShareLinksRequest>
+                        for GetWorkoutShareLinksSvc<T>
+                    {
+                        type Response = super::GetWorkoutShareLinksResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetWorkoutShareLinksRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut =
+                                async move { (*inner).get_workout_share_links(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetWorkoutShareLinksSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => Box::pin(async move {
+                    Ok(http::Response::builder()
+                        .status(200)
+                        .header("grpc-status", "12")
+                        .header("content-type", "application/grpc")
+                        .body(empty_body())
+                        .unwrap())
+                }),
+            }
+        }
+    }
+    impl<T: ShareLinkService> Clone for ShareLinkServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+            }
+        }
+    }
+    impl<T: ShareLinkService> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+    impl<T: ShareLinkService> tonic::transport::NamedService for ShareLinkServiceServer<T> {
+        const NAME: &'static str = "santa_cruz.ShareLinkService";
+    }
+}
+#[doc = r" Generated server implementations."]
+pub mod public_service_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    #[doc = "Generated trait containing gRPC methods that should be implemented for use with PublicServiceServer."]
+    #[async_trait]
+    pub trait PublicService: Send + Sync + 'static {
+        async fn get_shared_workout(
+            &self,
+            request: tonic::Request<super::GetSharedWorkoutRequest>,
+        ) -> Result<tonic::Response<super::SharedWorkout>, tonic::Status>;
+    }
+    #[doc = " Served without authentication, anyone knowing a slug can read what it points to."]
+    #[derive(Debug)]
+    pub struct PublicServiceServer<T: PublicService> {
+        inner: _Inner<T>,
+        accept_compression_encodings: (),
+        send_compression_encodings: (),
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: PublicService> PublicServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            let inner = Arc::new(inner);
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+            }
+        }
+        pub fn with_interceptor<F>(inner: T, interceptor: F) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for PublicServiceServer<T>
+    where
+        T: PublicService,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = Never;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/santa_cruz.PublicService/GetSharedWorkout" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetSharedWorkoutSvc<T: PublicService>(pub Arc<T>);
+                    impl<T: PublicService>
+                        tonic::server::UnaryService<super::GetSharedWorkoutRequest>
+                        for GetSharedWorkoutSvc<T>
+                    {
+                        type Response = super::SharedWorkout;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetSharedWorkoutRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_shared_workout(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetSharedWorkoutSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => Box::pin(async move {
+                    Ok(http::Response::builder()
+                        .status(200)
+                        .header("grpc-status", "12")
+                        .header("content-type", "application/grpc")
+                        .body(empty_body())
+                        .unwrap())
+                }),
+            }
+        }
+    }
+    impl<T: PublicService> Clone for PublicServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+            }
+        }
+    }
+    impl<T: PublicService> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+    impl<T: PublicService> tonic::transport::NamedService for PublicServiceServer<T> {
+        const NAME: &'static str = "santa_cruz.PublicService";
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InviteAthleteRequest {
+    #[prost(string, tag = "1")]
+    pub email: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AcceptCoachRequest {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RevokeCoachRelationshipRequest {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RevokeCoachRelationshipResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetCoachRelationshipsRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetCoachRelationshipsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub relationships: ::prost::alloc::vec::Vec<CoachRelationship>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetAthletesRequest {
+    /// number of recent workouts returned per athlete, defaults to 5
+    #[prost(int32, tag = "1")]
+    pub recent_workouts: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetAthletesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub athletes: ::prost::alloc::vec::Vec<Athlete>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CoachRelationship {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(string, tag = "2")]
+    pub created_at: ::prost::alloc::string::String,
+    #[prost(int32, tag = "3")]
+    pub coach_id: i32,
+    #[prost(string, tag = "4")]
+    pub coach_email: ::prost::alloc::string::String,
+    #[prost(int32, tag = "5")]
+    pub athlete_id: i32,
+    #[prost(string, tag = "6")]
+    pub athlete_email: ::prost::alloc::string::String,
+    #[prost(bool, tag = "7")]
+    pub accepted: bool,
+    #[prost(string, tag = "8")]
+    pub accepted_at: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Athlete {
+    #[prost(int32, tag = "1")]
+    pub user_id: i32,
+    #[prost(string, tag = "2")]
+    pub email: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub accepted_at: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "4")]
+    pub recent_workouts: ::prost::alloc::vec::Vec<Workout>,
 }
 #[doc = r" Generated server implementations."]
-pub mod workout_repeat_service_server {
+pub mod coach_service_server {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
     use tonic::codegen::*;
-    #[doc = "Generated trait containing gRPC methods that should be implemented for use with WorkoutRepeatServiceServer."]
+    #[doc = "Generated trait containing gRPC methods that should be implemented for use with CoachServiceServer."]
     #[async_trait]
-    pub trait WorkoutRepeatService: Send + Sync + 'static {
-        async fn get_workout_repeat(
+    pub trait CoachService: Send + Sync + 'static {
+        async fn invite_athlete(
             &self,
-            request: tonic::Request<super::GetWorkoutRepeatRequest>,
-        ) -> Result<tonic::Response<super::WorkoutRepeat>, tonic::Status>;
-        async fn create_workout_repeat(
+            request: tonic::Request<super::InviteAthleteRequest>,
+        ) -> Result<tonic::Response<super::CoachRelationship>, tonic::Status>;
+        async fn accept_coach(
             &self,
-            request: tonic::Request<super::CreateWorkoutRepeatRequest>,
-        ) -> Result<tonic::Response<super::WorkoutRepeat>, tonic::Status>;
-        async fn update_workout_repeat(
+            request: tonic::Request<super::AcceptCoachRequest>,
+        ) -> Result<tonic::Response<super::CoachRelationship>, tonic::Status>;
+        async fn revoke_coach_relationship(
             &self,
-            request: tonic::Request<super::UpdateWorkoutRepeatRequest>,
-        ) -> Result<tonic::Response<super::WorkoutRepeat>, tonic::Status>;
-        async fn delete_workout_repeat(
+            request: tonic::Request<super::RevokeCoachRelationshipRequest>,
+        ) -> Result<tonic::Response<super::RevokeCoachRelationshipResponse>, tonic::Status>;
+        async fn get_coach_relationships(
             &self,
-            request: tonic::Request<super::DeleteWorkoutRepeatRequest>,
-        ) -> Result<tonic::Response<super::DeleteWorkoutRepeatResponse>, tonic::Status>;
-        async fn get_workout_repeats(
+            request: tonic::Request<super::GetCoachRelationshipsRequest>,
+        ) -> Result<tonic::Response<super::GetCoachRelationshipsResponse>, tonic::Status>;
+        async fn get_athletes(
             &self,
-            request: tonic::Request<super::GetWorkoutRepeatsRequest>,
-        ) -> Result<tonic::Response<super::GetWorkoutRepeatsResponse>, tonic::Status>;
+            request: tonic::Request<super::GetAthletesRequest>,
+        ) -> Result<tonic::Response<super::GetAthletesResponse>, tonic::Status>;
     }
+    #[doc = " Accepted coaches get editor access to every workout their athletes own."]
     #[derive(Debug)]
-    pub struct WorkoutRepeatServiceServer<T: WorkoutRepeatService> {
+    pub struct CoachServiceServer<T: CoachService> {
         inner: _Inner<T>,
         accept_compression_encodings: (),
         send_compression_encodings: (),
     }
     struct _Inner<T>(Arc<T>);
-    impl<T: WorkoutRepeatService> WorkoutRepeatServiceServer<T> {
+    impl<T: CoachService> CoachServiceServer<T> {
         pub fn new(inner: T) -> Self {
             let inner = Arc::new(inner);
             let inner = _Inner(inner);
@@ -1147,9 +3614,9 @@ pub mod workout_repeat_service_server {
             InterceptedService::new(Self::new(inner), interceptor)
         }
     }
-    impl<T, B> tonic::codegen::Service<http::Request<B>> for WorkoutRepeatServiceServer<T>
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for CoachServiceServer<T>
     where
-        T: WorkoutRepeatService,
+        T: CoachService,
         B: Body + Send + 'static,
         B::Error: Into<StdError> + Send + 'static,
     {
@@ -1162,21 +3629,20 @@ pub mod workout_repeat_service_server {
         fn call(&mut self, req: http::Request<B>) -> Self::Future {
             let inner = self.inner.clone();
             match req.uri().path() {
-                "/santa_cruz.WorkoutRepeatService/GetWorkoutRepeat" => {
+                "/santa_cruz.CoachService/InviteAthlete" => {
                     #[allow(non_camel_case_types)]
-                    struct GetWorkoutRepeatSvc<T: WorkoutRepeatService>(pub Arc<T>);
-                    impl<T: WorkoutRepeatService>
-                        tonic::server::UnaryService<super::GetWorkoutRepeatRequest>
-                        for GetWorkoutRepeatSvc<T>
+                    struct InviteAthleteSvc<T: CoachService>(pub Arc<T>);
+                    impl<T: CoachService> tonic::server::UnaryService<super::InviteAthleteRequest>
+                        for InviteAthleteSvc<T>
                     {
-                        type Response = super::WorkoutRepeat;
+                        type Response = super::CoachRelationship;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::GetWorkoutRepeatRequest>,
+                            request: tonic::Request<super::InviteAthleteRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).get_workout_repeat(request).await };
+                            let fut = async move { (*inner).invite_athlete(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -1185,7 +3651,7 @@ pub mod workout_repeat_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = GetWorkoutRepeatSvc(inner);
+                        let method = InviteAthleteSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -1196,21 +3662,18 @@ pub mod workout_repeat_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/santa_cruz.WorkoutRepeatService/CreateWorkoutRepeat" => {
+                "/santa_cruz.CoachService/AcceptCoach" => {
                     #[allow(non_camel_case_types)]
-                    struct CreateWorkoutRepeatSvc<T: WorkoutRepeatService>(pub Arc<T>);
-                    impl<T: WorkoutRepeatService>
-                        tonic::server::UnaryService<super::CreateWorkoutRepeatRequest>
-                        for CreateWorkoutRepeatSvc<T>
-                    {
-                        type Response = super::WorkoutRepeat;
+                    struct AcceptCoachSvc<T: CoachService>(pub Arc<T>);
+                    impl<T: CoachService> tonic::server::UnaryService<super::AcceptCoachRequest> for AcceptCoachSvc<T> {
+                        type Response = super::CoachRelationship;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::CreateWorkoutRepeatRequest>,
+                            request: tonic::Request<super::AcceptCoachRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).create_workout_repeat(request).await };
+                            let fut = async move { (*inner).accept_coach(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -1219,7 +3682,7 @@ pub mod workout_repeat_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = CreateWorkoutRepeatSvc(inner);
+                        let method = AcceptCoachSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -1230,21 +3693,22 @@ pub mod workout_repeat_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/santa_cruz.WorkoutRepeatService/UpdateWorkoutRepeat" => {
+                "/santa_cruz.CoachService/RevokeCoachRelationship" => {
                     #[allow(non_camel_case_types)]
-                    struct UpdateWorkoutRepeatSvc<T: WorkoutRepeatService>(pub Arc<T>);
-                    impl<T: WorkoutRepeatService>
-                        tonic::server::UnaryService<super::UpdateWorkoutRepeatRequest>
-                        for UpdateWorkoutRepeatSvc<T>
+                    struct RevokeCoachRelationshipSvc<T: CoachService>(pub Arc<T>);
+                    impl<T: CoachService>
+                        tonic::server::UnaryService<super::RevokeCoachRelationshipRequest>
+                        for RevokeCoachRelationshipSvc<T>
                     {
-                        type Response = super::WorkoutRepeat;
+                        type Response = super::RevokeCoachRelationshipResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::UpdateWorkoutRepeatRequest>,
+                            request: tonic::Request<super::RevokeCoachRelationshipRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).update_workout_repeat(request).await };
+                            let fut =
+                                async move { (*inner).revoke_coach_relationship(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -1253,7 +3717,7 @@ pub mod workout_repeat_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = UpdateWorkoutRepeatSvc(inner);
+                        let method = RevokeCoachRelationshipSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -1264,21 +3728,22 @@ pub mod workout_repeat_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/santa_cruz.WorkoutRepeatService/DeleteWorkoutRepeat" => {
+                "/santa_cruz.CoachService/GetCoachRelationships" => {
                     #[allow(non_camel_case_types)]
-                    struct DeleteWorkoutRepeatSvc<T: WorkoutRepeatService>(pub Arc<T>);
-                    impl<T: WorkoutRepeatService>
-                        tonic::server::UnaryService<super::DeleteWorkoutRepeatRequest>
-                        for DeleteWorkoutRepeatSvc<T>
+                    struct GetCoachRelationshipsSvc<T: CoachService>(pub Arc<T>);
+                    impl<T: CoachService>
+                        tonic::server::UnaryService<super::GetCoachRelationshipsRequest>
+                        for GetCoachRelationshipsSvc<T>
                     {
-                        type Response = super::DeleteWorkoutRepeatResponse;
+                        type Response = super::GetCoachRelationshipsResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::DeleteWorkoutRepeatRequest>,
+                            request: tonic::Request<super::GetCoachRelationshipsRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).delete_workout_repeat(request).await };
+                            let fut =
+                                async move { (*inner).get_coach_relationships(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -1287,7 +3752,7 @@ pub mod workout_repeat_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = DeleteWorkoutRepeatSvc(inner);
+                        let method = GetCoachRelationshipsSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -1298,21 +3763,18 @@ pub mod workout_repeat_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/santa_cruz.WorkoutRepeatService/GetWorkoutRepeats" => {
+                "/santa_cruz.CoachService/GetAthletes" => {
                     #[allow(non_camel_case_types)]
-                    struct GetWorkoutRepeatsSvc<T: WorkoutRepeatService>(pub Arc<T>);
-                    impl<T: WorkoutRepeatService>
-                        tonic::server::UnaryService<super::GetWorkoutRepeatsRequest>
-                        for GetWorkoutRepeatsSvc<T>
-                    {
-                        type Response = super::GetWorkoutRepeatsResponse;
+                    struct GetAthletesSvc<T: CoachService>(pub Arc<T>);
+                    impl<T: CoachService> tonic::server::UnaryService<super::GetAthletesRequest> for GetAthletesSvc<T> {
+                        type Response = super::GetAthletesResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::GetWorkoutRepeatsRequest>,
+                            request: tonic::Request<super::GetAthletesRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).get_workout_repeats(request).await };
+                            let fut = async move { (*inner).get_athletes(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -1321,7 +3783,7 @@ pub mod workout_repeat_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = GetWorkoutRepeatsSvc(inner);
+                        let method = GetAthletesSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -1343,7 +3805,7 @@ pub mod workout_repeat_service_server {
             }
         }
     }
-    impl<T: WorkoutRepeatService> Clone for WorkoutRepeatServiceServer<T> {
+    impl<T: CoachService> Clone for CoachServiceServer<T> {
         fn clone(&self) -> Self {
             let inner = self.inner.clone();
             Self {
@@ -1353,7 +3815,7 @@ pub mod workout_repeat_service_server {
             }
         }
     }
-    impl<T: WorkoutRepeatService> Clone for _Inner<T> {
+    impl<T: CoachService> Clone for _Inner<T> {
         fn clone(&self) -> Self {
             Self(self.0.clone())
         }
@@ -1363,61 +3825,140 @@ pub mod workout_repeat_service_server {
             write!(f, "{:?}", self.0)
         }
     }
-    impl<T: WorkoutRepeatService> tonic::transport::NamedService for WorkoutRepeatServiceServer<T> {
-        const NAME: &'static str = "santa_cruz.WorkoutRepeatService";
+    impl<T: CoachService> tonic::transport::NamedService for CoachServiceServer<T> {
+        const NAME: &'static str = "santa_cruz.CoachService";
     }
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GetSessionsRequest {}
+pub struct CreateGroupRequest {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+}
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GetSessionsResponse {
+pub struct DeleteGroupRequest {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteGroupResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetGroupsRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetGroupsResponse {
     #[prost(message, repeated, tag = "1")]
-    pub sessions: ::prost::alloc::vec::Vec<Session>,
+    pub groups: ::prost::alloc::vec::Vec<Group>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct DeleteSessionRequest {
+pub struct GetGroupMembersRequest {
     #[prost(int32, tag = "1")]
-    pub id: i32,
+    pub group_id: i32,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct DeleteSessionResponse {}
+pub struct GetGroupMembersResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub members: ::prost::alloc::vec::Vec<GroupMember>,
+}
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct Session {
+pub struct AddGroupMemberRequest {
+    #[prost(int32, tag = "1")]
+    pub group_id: i32,
+    #[prost(string, tag = "2")]
+    pub email: ::prost::alloc::string::String,
+    #[prost(enumeration = "GroupRole", tag = "3")]
+    pub role: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateGroupMemberRequest {
+    #[prost(int32, tag = "1")]
+    pub group_id: i32,
+    #[prost(int32, tag = "2")]
+    pub member_id: i32,
+    #[prost(enumeration = "GroupRole", tag = "3")]
+    pub role: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RemoveGroupMemberRequest {
+    #[prost(int32, tag = "1")]
+    pub group_id: i32,
+    #[prost(int32, tag = "2")]
+    pub member_id: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Group {
     #[prost(int32, tag = "1")]
     pub id: i32,
     #[prost(string, tag = "2")]
     pub created_at: ::prost::alloc::string::String,
     #[prost(string, tag = "3")]
     pub updated_at: ::prost::alloc::string::String,
-    #[prost(int32, tag = "4")]
+    #[prost(string, tag = "4")]
+    pub name: ::prost::alloc::string::String,
+    /// role of the caller
+    #[prost(enumeration = "GroupRole", tag = "5")]
+    pub role: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GroupMember {
+    #[prost(int32, tag = "1")]
     pub user_id: i32,
-    #[prost(string, tag = "5")]
-    pub device_name: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub email: ::prost::alloc::string::String,
+    #[prost(enumeration = "GroupRole", tag = "3")]
+    pub role: i32,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum GroupRole {
+    Unspecified = 0,
+    Member = 1,
+    Manager = 2,
+    Owner = 3,
 }
 #[doc = r" Generated server implementations."]
-pub mod session_service_server {
+pub mod group_service_server {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
     use tonic::codegen::*;
-    #[doc = "Generated trait containing gRPC methods that should be implemented for use with SessionServiceServer."]
+    #[doc = "Generated trait containing gRPC methods that should be implemented for use with GroupServiceServer."]
     #[async_trait]
-    pub trait SessionService: Send + Sync + 'static {
-        async fn delete_session(
+    pub trait GroupService: Send + Sync + 'static {
+        async fn create_group(
             &self,
-            request: tonic::Request<super::DeleteSessionRequest>,
-        ) -> Result<tonic::Response<super::DeleteSessionResponse>, tonic::Status>;
-        async fn get_sessions(
+            request: tonic::Request<super::CreateGroupRequest>,
+        ) -> Result<tonic::Response<super::Group>, tonic::Status>;
+        async fn delete_group(
             &self,
-            request: tonic::Request<super::GetSessionsRequest>,
-        ) -> Result<tonic::Response<super::GetSessionsResponse>, tonic::Status>;
+            request: tonic::Request<super::DeleteGroupRequest>,
+        ) -> Result<tonic::Response<super::DeleteGroupResponse>, tonic::Status>;
+        async fn get_groups(
+            &self,
+            request: tonic::Request<super::GetGroupsRequest>,
+        ) -> Result<tonic::Response<super::GetGroupsResponse>, tonic::Status>;
+        async fn get_group_members(
+            &self,
+            request: tonic::Request<super::GetGroupMembersRequest>,
+        ) -> Result<tonic::Response<super::GetGroupMembersResponse>, tonic::Status>;
+        async fn add_group_member(
+            &self,
+            request: tonic::Request<super::AddGroupMemberRequest>,
+        ) -> Result<tonic::Response<super::GetGroupMembersResponse>, tonic::Status>;
+        async fn update_group_member(
+            &self,
+            request: tonic::Request<super::UpdateGroupMemberRequest>,
+        ) -> Result<tonic::Response<super::GetGroupMembersResponse>, tonic::Status>;
+        async fn remove_group_member(
+            &self,
+            request: tonic::Request<super::RemoveGroupMemberRequest>,
+        ) -> Result<tonic::Response<super::GetGroupMembersResponse>, tonic::Status>;
     }
+    #[doc = " Groups can be granted viewer or editor permissions on objects, shared with all their members."]
     #[derive(Debug)]
-    pub struct SessionServiceServer<T: SessionService> {
+    pub struct GroupServiceServer<T: GroupService> {
         inner: _Inner<T>,
         accept_compression_encodings: (),
         send_compression_encodings: (),
     }
     struct _Inner<T>(Arc<T>);
-    impl<T: SessionService> SessionServiceServer<T> {
+    impl<T: GroupService> GroupServiceServer<T> {
         pub fn new(inner: T) -> Self {
             let inner = Arc::new(inner);
             let inner = _Inner(inner);
@@ -1434,9 +3975,9 @@ pub mod session_service_server {
             InterceptedService::new(Self::new(inner), interceptor)
         }
     }
-    impl<T, B> tonic::codegen::Service<http::Request<B>> for SessionServiceServer<T>
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for GroupServiceServer<T>
     where
-        T: SessionService,
+        T: GroupService,
         B: Body + Send + 'static,
         B::Error: Into<StdError> + Send + 'static,
     {
@@ -1449,20 +3990,180 @@ pub mod session_service_server {
         fn call(&mut self, req: http::Request<B>) -> Self::Future {
             let inner = self.inner.clone();
             match req.uri().path() {
-                "/santa_cruz.SessionService/DeleteSession" => {
+                "/santa_cruz.GroupService/CreateGroup" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateGroupSvc<T: GroupService>(pub Arc<T>);
+                    impl<T: GroupService> tonic::server::UnaryService<super::CreateGroupRequest> for CreateGroupSvc<T> {
+                        type Response = super::Group;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreateGroupRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).create_group(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CreateGroupSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.GroupService/DeleteGroup" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteGroupSvc<T: GroupService>(pub Arc<T>);
+                    impl<T: GroupService> tonic::server::UnaryService<super::DeleteGroupRequest> for DeleteGroupSvc<T> {
+                        type Response = super::DeleteGroupResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeleteGroupRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).delete_group(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DeleteGroupSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.GroupService/GetGroups" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetGroupsSvc<T: GroupService>(pub Arc<T>);
+                    impl<T: GroupService> tonic::server::UnaryService<super::GetGroupsRequest> for GetGroupsSvc<T> {
+                        type Response = super::GetGroupsResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetGroupsRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_groups(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetGroupsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.GroupService/GetGroupMembers" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetGroupMembersSvc<T: GroupService>(pub Arc<T>);
+                    impl<T: GroupService> tonic::server::UnaryService<super::GetGroupMembersRequest>
+                        for GetGroupMembersSvc<T>
+                    {
+                        type Response = super::GetGroupMembersResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetGroupMembersRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_group_members(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetGroupMembersSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.GroupService/AddGroupMember" => {
+                    #[allow(non_camel_case_types)]
+                    struct AddGroupMemberSvc<T: GroupService>(pub Arc<T>);
+                    impl<T: GroupService> tonic::server::UnaryService<super::AddGroupMemberRequest>
+                        for AddGroupMemberSvc<T>
+                    {
+                        type Response = super::GetGroupMembersResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AddGroupMemberRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).add_group_member(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = AddGroupMemberSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.GroupService/UpdateGroupMember" => {
                     #[allow(non_camel_case_types)]
-                    struct DeleteSessionSvc<T: SessionService>(pub Arc<T>);
-                    impl<T: SessionService> tonic::server::UnaryService<super::DeleteSessionRequest>
-                        for DeleteSessionSvc<T>
+                    struct UpdateGroupMemberSvc<T: GroupService>(pub Arc<T>);
+                    impl<T: GroupService>
+                        tonic::server::UnaryService<super::UpdateGroupMemberRequest>
+                        for UpdateGroupMemberSvc<T>
                     {
-                        type Response = super::DeleteSessionResponse;
+                        type Response = super::GetGroupMembersResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::DeleteSessionRequest>,
+                            request: tonic::Request<super::UpdateGroupMemberRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).delete_session(request).await };
+                            let fut = async move { (*inner).update_group_member(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -1471,7 +4172,7 @@ pub mod session_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = DeleteSessionSvc(inner);
+                        let method = UpdateGroupMemberSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -1482,20 +4183,21 @@ pub mod session_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/santa_cruz.SessionService/GetSessions" => {
+                "/santa_cruz.GroupService/RemoveGroupMember" => {
                     #[allow(non_camel_case_types)]
-                    struct GetSessionsSvc<T: SessionService>(pub Arc<T>);
-                    impl<T: SessionService> tonic::server::UnaryService<super::GetSessionsRequest>
-                        for GetSessionsSvc<T>
+                    struct RemoveGroupMemberSvc<T: GroupService>(pub Arc<T>);
+                    impl<T: GroupService>
+                        tonic::server::UnaryService<super::RemoveGroupMemberRequest>
+                        for RemoveGroupMemberSvc<T>
                     {
-                        type Response = super::GetSessionsResponse;
+                        type Response = super::GetGroupMembersResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::GetSessionsRequest>,
+                            request: tonic::Request<super::RemoveGroupMemberRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).get_sessions(request).await };
+                            let fut = async move { (*inner).remove_group_member(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -1504,7 +4206,7 @@ pub mod session_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = GetSessionsSvc(inner);
+                        let method = RemoveGroupMemberSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -1526,7 +4228,7 @@ pub mod session_service_server {
             }
         }
     }
-    impl<T: SessionService> Clone for SessionServiceServer<T> {
+    impl<T: GroupService> Clone for GroupServiceServer<T> {
         fn clone(&self) -> Self {
             let inner = self.inner.clone();
             Self {
@@ -1536,7 +4238,7 @@ pub mod session_service_server {
             }
         }
     }
-    impl<T: SessionService> Clone for _Inner<T> {
+    impl<T: GroupService> Clone for _Inner<T> {
         fn clone(&self) -> Self {
             Self(self.0.clone())
         }
@@ -1546,43 +4248,78 @@ pub mod session_service_server {
             write!(f, "{:?}", self.0)
         }
     }
-    impl<T: SessionService> tonic::transport::NamedService for SessionServiceServer<T> {
-        const NAME: &'static str = "santa_cruz.SessionService";
+    impl<T: GroupService> tonic::transport::NamedService for GroupServiceServer<T> {
+        const NAME: &'static str = "santa_cruz.GroupService";
     }
 }
+/// Entries are returned newest first. Without access to the resource only the caller's own
+/// changes are listed.
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct MeRequest {}
+pub struct GetAuditLogRequest {
+    #[prost(string, tag = "1")]
+    pub resource_type: ::prost::alloc::string::String,
+    #[prost(int32, tag = "2")]
+    pub resource_id: i32,
+    /// defaults to 50, at most 200
+    #[prost(int32, tag = "3")]
+    pub page_size: i32,
+    /// `next_page_token` of the previous page
+    #[prost(string, tag = "4")]
+    pub page_token: ::prost::alloc::string::String,
+}
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct User {
-    #[prost(int32, tag = "1")]
-    pub id: i32,
+pub struct GetAuditLogResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub entries: ::prost::alloc::vec::Vec<AuditEntry>,
+    /// empty on the last page
+    #[prost(string, tag = "2")]
+    pub next_page_token: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AuditEntry {
+    #[prost(int64, tag = "1")]
+    pub id: i64,
     #[prost(string, tag = "2")]
     pub created_at: ::prost::alloc::string::String,
-    #[prost(string, tag = "3")]
-    pub updated_at: ::prost::alloc::string::String,
+    #[prost(int32, tag = "3")]
+    pub actor_id: i32,
     #[prost(string, tag = "4")]
-    pub email: ::prost::alloc::string::String,
+    pub rpc: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub resource_type: ::prost::alloc::string::String,
+    #[prost(int32, tag = "6")]
+    pub resource_id: i32,
+    /// JSON snapshots of the resource, empty before a create and after a delete
+    #[prost(string, tag = "7")]
+    pub before: ::prost::alloc::string::String,
+    #[prost(string, tag = "8")]
+    pub after: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag = "9")]
+    pub changed_fields: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// admin acting as actor_id through an impersonation session, 0 otherwise
+    #[prost(int32, tag = "10")]
+    pub impersonator_id: i32,
 }
 #[doc = r" Generated server implementations."]
-pub mod user_service_server {
+pub mod audit_service_server {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
     use tonic::codegen::*;
-    #[doc = "Generated trait containing gRPC methods that should be implemented for use with UserServiceServer."]
+    #[doc = "Generated trait containing gRPC methods that should be implemented for use with AuditServiceServer."]
     #[async_trait]
-    pub trait UserService: Send + Sync + 'static {
-        async fn me(
+    pub trait AuditService: Send + Sync + 'static {
+        async fn get_audit_log(
             &self,
-            request: tonic::Request<super::MeRequest>,
-        ) -> Result<tonic::Response<super::User>, tonic::Status>;
+            request: tonic::Request<super::GetAuditLogRequest>,
+        ) -> Result<tonic::Response<super::GetAuditLogResponse>, tonic::Status>;
     }
     #[derive(Debug)]
-    pub struct UserServiceServer<T: UserService> {
+    pub struct AuditServiceServer<T: AuditService> {
         inner: _Inner<T>,
         accept_compression_encodings: (),
         send_compression_encodings: (),
     }
     struct _Inner<T>(Arc<T>);
-    impl<T: UserService> UserServiceServer<T> {
+    impl<T: AuditService> AuditServiceServer<T> {
         pub fn new(inner: T) -> Self {
             let inner = Arc::new(inner);
             let inner = _Inner(inner);
@@ -1599,9 +4336,9 @@ pub mod user_service_server {
             InterceptedService::new(Self::new(inner), interceptor)
         }
     }
-    impl<T, B> tonic::codegen::Service<http::Request<B>> for UserServiceServer<T>
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for AuditServiceServer<T>
     where
-        T: UserService,
+        T: AuditService,
         B: Body + Send + 'static,
         B::Error: Into<StdError> + Send + 'static,
     {
@@ -1614,18 +4351,18 @@ pub mod user_service_server {
         fn call(&mut self, req: http::Request<B>) -> Self::Future {
             let inner = self.inner.clone();
             match req.uri().path() {
-                "/santa_cruz.UserService/Me" => {
+                "/santa_cruz.AuditService/GetAuditLog" => {
                     #[allow(non_camel_case_types)]
-                    struct MeSvc<T: UserService>(pub Arc<T>);
-                    impl<T: UserService> tonic::server::UnaryService<super::MeRequest> for MeSvc<T> {
-                        type Response = super::User;
+                    struct GetAuditLogSvc<T: AuditService>(pub Arc<T>);
+                    impl<T: AuditService> tonic::server::UnaryService<super::GetAuditLogRequest> for GetAuditLogSvc<T> {
+                        type Response = super::GetAuditLogResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::MeRequest>,
+                            request: tonic::Request<super::GetAuditLogRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).me(request).await };
+                            let fut = async move { (*inner).get_audit_log(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -1634,7 +4371,7 @@ pub mod user_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = MeSvc(inner);
+                        let method = GetAuditLogSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -1656,7 +4393,7 @@ pub mod user_service_server {
             }
         }
     }
-    impl<T: UserService> Clone for UserServiceServer<T> {
+    impl<T: AuditService> Clone for AuditServiceServer<T> {
         fn clone(&self) -> Self {
             let inner = self.inner.clone();
             Self {
@@ -1666,7 +4403,7 @@ pub mod user_service_server {
             }
         }
     }
-    impl<T: UserService> Clone for _Inner<T> {
+    impl<T: AuditService> Clone for _Inner<T> {
         fn clone(&self) -> Self {
             Self(self.0.clone())
         }
@@ -1676,54 +4413,152 @@ pub mod user_service_server {
             write!(f, "{:?}", self.0)
         }
     }
-    impl<T: UserService> tonic::transport::NamedService for UserServiceServer<T> {
-        const NAME: &'static str = "santa_cruz.UserService";
+    impl<T: AuditService> tonic::transport::NamedService for AuditServiceServer<T> {
+        const NAME: &'static str = "santa_cruz.AuditService";
     }
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct LoginRequest {
+pub struct GetUsersRequest {
+    /// case-insensitive part of the email, empty lists everyone
     #[prost(string, tag = "1")]
-    pub email: ::prost::alloc::string::String,
-    #[prost(string, tag = "2")]
-    pub password: ::prost::alloc::string::String,
+    pub query: ::prost::alloc::string::String,
+    /// defaults to 50, at most 200
+    #[prost(int32, tag = "2")]
+    pub page_size: i32,
     #[prost(string, tag = "3")]
-    pub token: ::prost::alloc::string::String,
-    #[prost(string, tag = "4")]
-    pub device_name: ::prost::alloc::string::String,
+    pub page_token: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct LogoutRequest {
+pub struct GetUsersResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub users: ::prost::alloc::vec::Vec<AdminUser>,
+    /// empty on the last page
+    #[prost(string, tag = "2")]
+    pub next_page_token: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DisableUserRequest {
+    #[prost(int32, tag = "1")]
+    pub user_id: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EnableUserRequest {
+    #[prost(int32, tag = "1")]
+    pub user_id: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RevokeUserSessionsRequest {
+    #[prost(int32, tag = "1")]
+    pub user_id: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RevokeUserSessionsResponse {
+    #[prost(int32, tag = "1")]
+    pub revoked: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetUserResourceCountsRequest {
+    #[prost(int32, tag = "1")]
+    pub user_id: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ImpersonateUserRequest {
     #[prost(int32, tag = "1")]
     pub user_id: i32,
+    /// shown to the user in their session list
     #[prost(string, tag = "2")]
-    pub token: ::prost::alloc::string::String,
+    pub reason: ::prost::alloc::string::String,
 }
+/// Short-lived access token acting as the user, it can't be refreshed nor manage credentials.
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct LogoutResponse {}
+pub struct ImpersonateUserResponse {
+    #[prost(string, tag = "1")]
+    pub access_token: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub expires_at: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "3")]
+    pub user: ::core::option::Option<AdminUser>,
+}
+/// Objects owned by the user, plus their credentials and memberships.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UserResourceCounts {
+    #[prost(int32, tag = "1")]
+    pub user_id: i32,
+    #[prost(int64, tag = "2")]
+    pub workouts: i64,
+    #[prost(int64, tag = "3")]
+    pub workout_sets: i64,
+    #[prost(int64, tag = "4")]
+    pub workout_repeats: i64,
+    #[prost(int64, tag = "5")]
+    pub exercises: i64,
+    #[prost(int64, tag = "6")]
+    pub sessions: i64,
+    #[prost(int64, tag = "7")]
+    pub api_keys: i64,
+    #[prost(int64, tag = "8")]
+    pub groups: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AdminUser {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(string, tag = "2")]
+    pub created_at: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub updated_at: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub email: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub role: ::prost::alloc::string::String,
+    #[prost(bool, tag = "6")]
+    pub two_factor_enabled: bool,
+    #[prost(bool, tag = "7")]
+    pub disabled: bool,
+    #[prost(string, tag = "8")]
+    pub disabled_at: ::prost::alloc::string::String,
+}
 #[doc = r" Generated server implementations."]
-pub mod auth_service_server {
+pub mod admin_service_server {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
     use tonic::codegen::*;
-    #[doc = "Generated trait containing gRPC methods that should be implemented for use with AuthServiceServer."]
+    #[doc = "Generated trait containing gRPC methods that should be implemented for use with AdminServiceServer."]
     #[async_trait]
-    pub trait AuthService: Send + Sync + 'static {
-        async fn login(
+    pub trait AdminService: Send + Sync + 'static {
+        async fn get_users(
             &self,
-            request: tonic::Request<super::LoginRequest>,
-        ) -> Result<tonic::Response<super::User>, tonic::Status>;
-        async fn logout(
+            request: tonic::Request<super::GetUsersRequest>,
+        ) -> Result<tonic::Response<super::GetUsersResponse>, tonic::Status>;
+        async fn disable_user(
             &self,
-            request: tonic::Request<super::LogoutRequest>,
-        ) -> Result<tonic::Response<super::LogoutResponse>, tonic::Status>;
+            request: tonic::Request<super::DisableUserRequest>,
+        ) -> Result<tonic::Response<super::AdminUser>, tonic::Status>;
+        async fn enable_user(
+            &self,
+            request: tonic::Request<super::EnableUserRequest>,
+        ) -> Result<tonic::Response<super::AdminUser>, tonic::Status>;
+        async fn revoke_user_sessions(
+            &self,
+            request: tonic::Request<super::RevokeUserSessionsRequest>,
+        ) -> Result<tonic::Response<super::RevokeUserSessionsResponse>, tonic::Status>;
+        async fn get_user_resource_counts(
+            &self,
+            request: tonic::Request<super::GetUserResourceCountsRequest>,
+        ) -> Result<tonic::Response<super::UserResourceCounts>, tonic::Status>;
+        async fn impersonate_user(
+            &self,
+            request: tonic::Request<super::ImpersonateUserRequest>,
+        ) -> Result<tonic::Response<super::ImpersonateUserResponse>, tonic::Status>;
     }
+    #[doc = " Operator tooling, restricted to users with the admin role."]
     #[derive(Debug)]
-    pub struct AuthServiceServer<T: AuthService> {
+    pub struct AdminServiceServer<T: AdminService> {
         inner: _Inner<T>,
         accept_compression_encodings: (),
         send_compression_encodings: (),
     }
     struct _Inner<T>(Arc<T>);
-    impl<T: AuthService> AuthServiceServer<T> {
+    impl<T: AdminService> AdminServiceServer<T> {
         pub fn new(inner: T) -> Self {
             let inner = Arc::new(inner);
             let inner = _Inner(inner);
@@ -1740,9 +4575,9 @@ pub mod auth_service_server {
             InterceptedService::new(Self::new(inner), interceptor)
         }
     }
-    impl<T, B> tonic::codegen::Service<http::Request<B>> for AuthServiceServer<T>
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for AdminServiceServer<T>
     where
-        T: AuthService,
+        T: AdminService,
         B: Body + Send + 'static,
         B::Error: Into<StdError> + Send + 'static,
     {
@@ -1755,18 +4590,18 @@ pub mod auth_service_server {
         fn call(&mut self, req: http::Request<B>) -> Self::Future {
             let inner = self.inner.clone();
             match req.uri().path() {
-                "/santa_cruz.AuthService/Login" => {
+                "/santa_cruz.AdminService/GetUsers" => {
                     #[allow(non_camel_case_types)]
-                    struct LoginSvc<T: AuthService>(pub Arc<T>);
-                    impl<T: AuthService> tonic::server::UnaryService<super::LoginRequest> for LoginSvc<T> {
-                        type Response = super::User;
+                    struct GetUsersSvc<T: AdminService>(pub Arc<T>);
+                    impl<T: AdminService> tonic::server::UnaryService<super::GetUsersRequest> for GetUsersSvc<T> {
+                        type Response = super::GetUsersResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::LoginRequest>,
+                            request: tonic::Request<super::GetUsersRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).login(request).await };
+                            let fut = async move { (*inner).get_users(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -1775,7 +4610,7 @@ pub mod auth_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = LoginSvc(inner);
+                        let method = GetUsersSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -1786,18 +4621,18 @@ pub mod auth_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/santa_cruz.AuthService/Logout" => {
+                "/santa_cruz.AdminService/DisableUser" => {
                     #[allow(non_camel_case_types)]
-                    struct LogoutSvc<T: AuthService>(pub Arc<T>);
-                    impl<T: AuthService> tonic::server::UnaryService<super::LogoutRequest> for LogoutSvc<T> {
-                        type Response = super::LogoutResponse;
+                    struct DisableUserSvc<T: AdminService>(pub Arc<T>);
+                    impl<T: AdminService> tonic::server::UnaryService<super::DisableUserRequest> for DisableUserSvc<T> {
+                        type Response = super::AdminUser;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::LogoutRequest>,
+                            request: tonic::Request<super::DisableUserRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).logout(request).await };
+                            let fut = async move { (*inner).disable_user(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -1806,7 +4641,140 @@ pub mod auth_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = LogoutSvc(inner);
+                        let method = DisableUserSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.AdminService/EnableUser" => {
+                    #[allow(non_camel_case_types)]
+                    struct EnableUserSvc<T: AdminService>(pub Arc<T>);
+                    impl<T: AdminService> tonic::server::UnaryService<super::EnableUserRequest> for EnableUserSvc<T> {
+                        type Response = super::AdminUser;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::EnableUserRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).enable_user(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = EnableUserSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.AdminService/RevokeUserSessions" => {
+                    #[allow(non_camel_case_types)]
+                    struct RevokeUserSessionsSvc<T: AdminService>(pub Arc<T>);
+                    impl<T: AdminService>
+                        tonic::server::UnaryService<super::RevokeUserSessionsRequest>
+                        for RevokeUserSessionsSvc<T>
+                    {
+                        type Response = super::RevokeUserSessionsResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RevokeUserSessionsRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).revoke_user_sessions(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = RevokeUserSessionsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.AdminService/GetUserResourceCounts" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetUserResourceCountsSvc<T: AdminService>(pub Arc<T>);
+                    impl<T: AdminService>
+                        tonic::server::UnaryService<super::GetUserResourceCountsRequest>
+                        for GetUserResourceCountsSvc<T>
+                    {
+                        type Response = super::UserResourceCounts;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetUserResourceCountsRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut =
+                                async move { (*inner).get_user_resource_counts(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetUserResourceCountsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/santa_cruz.AdminService/ImpersonateUser" => {
+                    #[allow(non_camel_case_types)]
+                    struct ImpersonateUserSvc<T: AdminService>(pub Arc<T>);
+                    impl<T: AdminService> tonic::server::UnaryService<super::ImpersonateUserRequest>
+                        for ImpersonateUserSvc<T>
+                    {
+                        type Response = super::ImpersonateUserResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ImpersonateUserRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).impersonate_user(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ImpersonateUserSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -1828,7 +4796,7 @@ pub mod auth_service_server {
             }
         }
     }
-    impl<T: AuthService> Clone for AuthServiceServer<T> {
+    impl<T: AdminService> Clone for AdminServiceServer<T> {
         fn clone(&self) -> Self {
             let inner = self.inner.clone();
             Self {
@@ -1838,7 +4806,7 @@ pub mod auth_service_server {
             }
         }
     }
-    impl<T: AuthService> Clone for _Inner<T> {
+    impl<T: AdminService> Clone for _Inner<T> {
         fn clone(&self) -> Self {
             Self(self.0.clone())
         }
@@ -1848,7 +4816,7 @@ pub mod auth_service_server {
             write!(f, "{:?}", self.0)
         }
     }
-    impl<T: AuthService> tonic::transport::NamedService for AuthServiceServer<T> {
-        const NAME: &'static str = "santa_cruz.AuthService";
+    impl<T: AdminService> tonic::transport::NamedService for AdminServiceServer<T> {
+        const NAME: &'static str = "santa_cruz.AdminService";
     }
 }
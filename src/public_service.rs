@@ -0,0 +1,95 @@
+use sqlx::PgPool;
+use tonic::{Request, Response, Status};
+
+use crate::errors;
+use crate::proto::proto::santa_cruz;
+use crate::proto::proto::santa_cruz::{
+    GetSharedWorkoutRequest, SharedWorkout, SharedWorkoutSet, Workout, WorkoutRepeat,
+};
+use crate::workout_set_service::WorkoutSetRow;
+use crate::Queryable;
+
+/// Read-only access to shared content, served without `AuthInterceptor`.
+pub struct PublicService {
+    pool: PgPool,
+}
+
+impl PublicService {
+    pub fn new(pool: &PgPool) -> PublicService {
+        PublicService { pool: pool.clone() }
+    }
+}
+
+#[tonic::async_trait]
+impl santa_cruz::public_service_server::PublicService for PublicService {
+    async fn get_shared_workout(
+        &self,
+        request: Request<GetSharedWorkoutRequest>,
+    ) -> Result<Response<SharedWorkout>, Status> {
+        let GetSharedWorkoutRequest { slug } = request.get_ref();
+
+        let link: Option<(i32,)> =
+            sqlx::query_as(r#"SELECT workout_id FROM workout_share_links WHERE slug = $1"#)
+                .bind(slug)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(errors::internal)?;
+
+        let (workout_id,) = link.ok_or_else(|| Status::not_found("share link not found"))?;
+
+        let mut query_builder = Workout::query();
+        query_builder.where_eq("id", workout_id);
+
+        let sql = query_builder.select_query();
+
+        let workout: Workout = sqlx::query_with(sql.0.as_str(), sql.1)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(errors::internal)?
+            .ok_or_else(|| Status::not_found("share link not found"))?
+            .into();
+
+        let sets: Vec<WorkoutSetRow> = sqlx::query_as(
+            r#"SELECT id, workout_id, position, type, comment, created_at, updated_at FROM workout_sets WHERE workout_id = $1 ORDER BY position, id"#,
+        )
+        .bind(workout_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(errors::internal)?;
+
+        let set_ids: Vec<i32> = sets.iter().map(|row| row.0).collect();
+
+        let repeats: Vec<WorkoutRepeat> = sqlx::query(
+            r#"SELECT id, created_at, updated_at, workout_set_id, exercise_id, repeats, weight, time FROM workout_repeats WHERE workout_set_id = ANY($1) ORDER BY id"#,
+        )
+        .bind(&set_ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(errors::internal)?
+        .into_iter()
+        .map(|row| row.into())
+        .collect();
+
+        let workout_sets = sets
+            .into_iter()
+            .map(|row| {
+                let workout_set: santa_cruz::WorkoutSet = row.into();
+                let workout_repeats = repeats
+                    .iter()
+                    .filter(|repeat| repeat.workout_set_id == workout_set.id)
+                    .cloned()
+                    .collect();
+
+                SharedWorkoutSet {
+                    workout_set: Some(workout_set),
+                    workout_repeats,
+                }
+            })
+            .collect();
+
+        Ok(Response::new(SharedWorkout {
+            workout: Some(workout),
+            workout_sets,
+        }))
+    }
+}
@@ -1,18 +1,71 @@
 use sqlx::postgres::PgArguments;
+use sqlx::types::Json;
 use sqlx::{Arguments, Encode, Postgres, Type};
 
+use crate::pagination::Cursor;
+use crate::queryable::FieldKind;
+use crate::sharing;
+use crate::sharing::Level;
+use crate::sorting::{Direction, Order};
+
 #[derive(Clone)]
 pub struct Field {
     value: String,
     position: usize,
 }
 
+/// Node of a `WHERE` clause. Leaves built by a `QueryBuilder` bind their arguments right away,
+/// so placeholders stay numbered correctly however the tree is composed afterwards.
+#[derive(Debug, Clone)]
+pub enum Condition {
+    Sql(String),
+    And(Vec<Condition>),
+    Or(Vec<Condition>),
+    Not(Box<Condition>),
+}
+
+impl Condition {
+    pub fn is_null<F>(field: F) -> Condition
+    where
+        F: Into<String>,
+    {
+        Condition::Sql(format!("{} IS NULL", field.into()))
+    }
+
+    pub fn to_sql(&self) -> String {
+        match self {
+            Condition::Sql(sql) => sql.clone(),
+            Condition::And(conditions) => Condition::join(conditions, " AND ", "TRUE"),
+            Condition::Or(conditions) => Condition::join(conditions, " OR ", "FALSE"),
+            Condition::Not(condition) => format!("NOT {}", condition.to_sql()),
+        }
+    }
+
+    fn join(conditions: &[Condition], operator: &str, empty: &str) -> String {
+        if conditions.is_empty() {
+            return empty.to_string();
+        }
+
+        format!(
+            "({})",
+            conditions
+                .iter()
+                .map(Condition::to_sql)
+                .collect::<Vec<String>>()
+                .join(operator)
+        )
+    }
+}
+
 #[derive(Default)]
 pub struct QueryBuilder {
     table: String,
     pointer: usize,
     fields: Vec<Field>,
-    filters: Vec<Field>,
+    joins: Vec<(String, Condition)>,
+    filters: Vec<Condition>,
+    order: Vec<Order>,
+    limit: Option<String>,
     arguments: PgArguments,
 }
 
@@ -60,22 +113,75 @@ impl QueryBuilder {
         self.pointer += 1;
     }
 
-    pub fn where_raw<'q, F, V>(&mut self, field: F, value: V)
+    /// Adds `value` to the arguments and returns its placeholder.
+    fn argument<'q, V>(&mut self, value: V) -> String
     where
-        F: Into<String>,
         V: Encode<'q, Postgres> + Send + Sync + Type<Postgres> + 'q,
     {
-        self.filters.push(Field {
-            value: str::replace(
-                field.into().as_str(),
-                "{index}",
-                self.pointer.to_string().as_str(),
-            ),
-            position: self.pointer,
-        });
+        let placeholder = format!("${}", self.pointer);
         self.arguments.add(value);
 
         self.pointer += 1;
+
+        placeholder
+    }
+
+    /// `field operator value`, `operator` is written as is and must never come from a request.
+    pub fn compare<'q, F, V>(&mut self, field: F, operator: &str, value: V) -> Condition
+    where
+        F: Into<String>,
+        V: Encode<'q, Postgres> + Send + Sync + Type<Postgres> + 'q,
+    {
+        Condition::Sql(format!(
+            "{} {} {}",
+            field.into(),
+            operator,
+            self.argument(value)
+        ))
+    }
+
+    pub fn eq<'q, F, V>(&mut self, field: F, value: V) -> Condition
+    where
+        F: Into<String>,
+        V: Encode<'q, Postgres> + Send + Sync + Type<Postgres> + 'q,
+    {
+        self.compare(field, "=", value)
+    }
+
+    pub fn any<'q, F, V>(&mut self, field: F, values: V) -> Condition
+    where
+        F: Into<String>,
+        V: Encode<'q, Postgres> + Send + Sync + Type<Postgres> + 'q,
+    {
+        Condition::Sql(format!("{} = ANY({})", field.into(), self.argument(values)))
+    }
+
+    /// Left joins `table` to selects, fields of joined tables have to be qualified and aliased.
+    pub fn left_join<T>(&mut self, table: T, on: Condition)
+    where
+        T: Into<String>,
+    {
+        self.joins.push((table.into(), on));
+    }
+
+    /// Condition requiring `level` on the rows of `table`, the selected one or a joined one,
+    /// see `sharing::permission_condition`.
+    pub fn permission(&mut self, table: &str, level: Level, user_id: i32) -> Condition {
+        let user = self.argument(user_id);
+
+        Condition::Sql(sharing::permission_condition(table, level, &user))
+    }
+
+    pub fn where_condition(&mut self, condition: Condition) {
+        self.filters.push(condition);
+    }
+
+    /// Keeps the rows `user_id` was granted `level` on, see `sharing::permission_condition`.
+    pub fn where_permission(&mut self, level: Level, user_id: i32) {
+        let table = self.table.clone();
+        let condition = self.permission(&table, level, user_id);
+
+        self.where_condition(condition);
     }
 
     pub fn where_eq<'q, F, V>(&mut self, field: F, value: V)
@@ -83,49 +189,87 @@ impl QueryBuilder {
         F: Into<String>,
         V: Encode<'q, Postgres> + Send + Sync + Type<Postgres> + 'q,
     {
-        self.filters.push(Field {
-            value: format!(
-                "{field} = ${index}",
-                field = field.into(),
-                index = self.pointer
-            ),
-            position: self.pointer,
-        });
-        self.arguments.add(value);
+        let condition = self.eq(field, value);
 
-        self.pointer += 1;
+        self.where_condition(condition);
     }
 
-    pub fn where_any<'q, F, V>(&mut self, field: F, value: V)
+    pub fn where_any<'q, F, V>(&mut self, field: F, values: V)
     where
         F: Into<String>,
         V: Encode<'q, Postgres> + Send + Sync + Type<Postgres> + 'q,
     {
-        self.filters.push(Field {
-            value: format!(
-                "{field} = ANY(${index})",
-                field = field.into(),
-                index = self.pointer
-            ),
-            position: self.pointer,
-        });
-        self.arguments.add(value);
+        let condition = self.any(field, values);
 
-        self.pointer += 1;
+        self.where_condition(condition);
     }
 
-    pub fn get_where_query(filters: Vec<Field>) -> String {
-        filters
-            .into_iter()
-            .map(|field| {
-                format!(
-                    "{field} = ${index}",
-                    field = field.value,
-                    index = field.position
-                )
-            })
-            .collect::<Vec<String>>()
-            .join(" AND ")
+    pub fn order_by(&mut self, orders: Vec<Order>) {
+        self.order.extend(orders);
+    }
+
+    pub fn limit(&mut self, limit: i64) {
+        self.limit = Some(self.argument(limit));
+    }
+
+    /// Keyset pagination over the current order, ties broken by `id`: the rows after `cursor`,
+    /// plus one telling whether another page follows, see `pagination::next_page_token`.
+    /// The sorted columns are selected as `page_cursor` and read back into the cursor with the
+    /// table's own types, `kinds` tells which values each of them accepts.
+    pub fn paginate(
+        &mut self,
+        page_size: i32,
+        cursor: Option<&Cursor>,
+        kinds: &[(&str, FieldKind)],
+    ) -> Result<(), &'static str> {
+        self.field(format!(
+            "jsonb_build_object({}) AS page_cursor",
+            self.order
+                .iter()
+                .map(|order| format!("'{0}', {0}", order.field))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ));
+
+        if let Some(cursor) = cursor {
+            cursor.check(&self.order, kinds)?;
+
+            let values = self.argument(Json(&cursor.values));
+            let row = format!("(jsonb_populate_record(NULL::{}, {}))", self.table, values);
+
+            let mut equal: Vec<Condition> = vec![];
+            let mut after: Vec<Condition> = vec![];
+
+            for order in &self.order {
+                let value = format!("{}.{}", row, order.field);
+
+                after.push(Condition::And(
+                    equal
+                        .iter()
+                        .cloned()
+                        .chain(Some(Condition::Sql(order.after_sql(&value))))
+                        .collect(),
+                ));
+                equal.push(Condition::Sql(format!(
+                    "{} IS NOT DISTINCT FROM {}",
+                    order.field, value
+                )));
+            }
+
+            equal.push(self.compare("id", ">", cursor.id));
+            after.push(Condition::And(equal));
+
+            self.where_condition(Condition::Or(after));
+        }
+
+        self.order.push(Order::new("id", Direction::Asc));
+        self.limit(i64::from(page_size) + 1);
+
+        Ok(())
+    }
+
+    pub fn get_where_query(filters: Vec<Condition>) -> String {
+        Condition::And(filters).to_sql()
     }
 
     pub fn has_fields(&self) -> bool {
@@ -140,12 +284,12 @@ impl QueryBuilder {
             .collect::<Vec<Field>>();
 
         let sql = format!(
-            "INSERT INTO {table} SET ({insert_fields}) VALUES ( {insert_indexes} ) RETURNING id",
+            "INSERT INTO {table} ({insert_fields}) VALUES ( {insert_indexes} ) RETURNING id",
             table = self.table,
             insert_fields = fields
                 .clone()
                 .into_iter()
-                .map(|f| format!("${}", f.value))
+                .map(|f| f.value)
                 .collect::<Vec<String>>()
                 .join(", "),
             insert_indexes = fields
@@ -194,9 +338,15 @@ impl QueryBuilder {
             .filter(|f| f.position == 0)
             .collect::<Vec<Field>>();
 
-        let sql = format!(
-            "SELECT {select_fields} FROM {table}  WHERE {filters}",
+        let mut sql = format!(
+            "SELECT {select_fields} FROM {table} {joins} WHERE {filters}",
             table = self.table,
+            joins = self
+                .joins
+                .iter()
+                .map(|(table, on)| format!("LEFT JOIN {} ON {}", table, on.to_sql()))
+                .collect::<Vec<String>>()
+                .join(" "),
             select_fields = fields
                 .into_iter()
                 .map(|f| f.value)
@@ -205,6 +355,21 @@ impl QueryBuilder {
             filters = QueryBuilder::get_where_query(self.filters),
         );
 
+        if !self.order.is_empty() {
+            sql.push_str(&format!(
+                " ORDER BY {}",
+                self.order
+                    .iter()
+                    .map(Order::to_sql)
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ));
+        }
+
+        if let Some(limit) = self.limit {
+            sql.push_str(&format!(" LIMIT {}", limit));
+        }
+
         (sql, self.arguments)
     }
 
@@ -218,3 +383,148 @@ impl QueryBuilder {
         (sql, self.arguments)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Map};
+
+    use super::*;
+
+    fn sql(condition: &str) -> Condition {
+        Condition::Sql(condition.to_string())
+    }
+
+    #[test]
+    fn renders_condition_trees() {
+        let condition = Condition::And(vec![
+            sql("a"),
+            Condition::Or(vec![sql("b"), Condition::Not(Box::new(sql("c")))]),
+        ]);
+
+        assert_eq!(condition.to_sql(), "(a AND (b OR NOT c))");
+        assert_eq!(Condition::And(vec![]).to_sql(), "TRUE");
+        assert_eq!(Condition::Or(vec![]).to_sql(), "FALSE");
+        assert_eq!(Condition::is_null("rate").to_sql(), "rate IS NULL");
+    }
+
+    #[test]
+    fn numbers_placeholders_in_binding_order() {
+        let mut query_builder = QueryBuilder::new("workouts");
+        query_builder.field("id");
+
+        let rate = query_builder.compare("rate", ">=", 3);
+        let status = query_builder.eq("status", "finished");
+        let ids = query_builder.any("id", vec![1, 2]);
+
+        // the tree is composed after binding, placeholders keep their numbers
+        query_builder.where_condition(Condition::Or(vec![
+            Condition::Not(Box::new(ids)),
+            Condition::And(vec![status, rate]),
+        ]));
+        query_builder.where_eq("comment", "");
+        query_builder.limit(10);
+
+        assert_eq!(
+            query_builder.select_query().0,
+            "SELECT id FROM workouts  WHERE ((NOT id = ANY($3) OR (status = $2 AND rate >= $1)) AND comment = $4) LIMIT $5"
+        );
+    }
+
+    #[test]
+    fn update_and_delete_share_the_conditions() {
+        let mut query_builder = QueryBuilder::new("workouts");
+        query_builder.field_with_argument("rate", 5);
+
+        assert!(query_builder.has_fields());
+
+        query_builder.where_eq("id", 1);
+
+        assert_eq!(
+            query_builder.update_query().0,
+            "UPDATE workouts SET rate = $1 WHERE (id = $2)"
+        );
+
+        let mut query_builder = QueryBuilder::new("workouts");
+        query_builder.where_eq("id", 1);
+
+        assert_eq!(
+            query_builder.delete_query().0,
+            "DELETE FROM workouts WHERE (id = $1)"
+        );
+    }
+
+    #[test]
+    fn left_joins_bind_their_conditions() {
+        let mut query_builder = QueryBuilder::new("workouts");
+        query_builder.field("workouts.id AS id");
+
+        let position = query_builder.eq("workout_sets.position", 1);
+        query_builder.left_join(
+            "workout_sets",
+            Condition::And(vec![sql("workout_sets.workout_id = workouts.id"), position]),
+        );
+        query_builder.where_eq("workouts.id", 2);
+
+        assert_eq!(
+            query_builder.select_query().0,
+            "SELECT workouts.id AS id FROM workouts LEFT JOIN workout_sets ON (workout_sets.workout_id = workouts.id AND workout_sets.position = $1) WHERE (workouts.id = $2)"
+        );
+    }
+
+    #[test]
+    fn paginates_the_first_page_by_id() {
+        let mut query_builder = QueryBuilder::new("workout_sets");
+        query_builder.field("id");
+        query_builder.where_eq("workout_id", 2);
+        query_builder.paginate(10, None, &[]).unwrap();
+
+        assert_eq!(
+            query_builder.select_query().0,
+            "SELECT id, jsonb_build_object() AS page_cursor FROM workout_sets  WHERE (workout_id = $1) ORDER BY id ASC NULLS LAST LIMIT $2"
+        );
+    }
+
+    #[test]
+    fn paginates_after_the_cursor_with_null_aware_keys() {
+        let mut values = Map::new();
+        values.insert("rate".to_string(), json!(5));
+
+        let mut query_builder = QueryBuilder::new("workouts");
+        query_builder.field("id");
+        query_builder.order_by(vec![Order::new("rate", Direction::Desc)]);
+        query_builder
+            .paginate(
+                10,
+                Some(&Cursor { id: 3, values }),
+                &[("rate", FieldKind::Int)],
+            )
+            .unwrap();
+
+        let row = "(jsonb_populate_record(NULL::workouts, $1))";
+
+        assert_eq!(
+            query_builder.select_query().0,
+            format!(
+                "SELECT id, jsonb_build_object('rate', rate) AS page_cursor FROM workouts  WHERE ((((rate < {row}.rate OR ({row}.rate IS NULL AND rate IS NOT NULL))) OR (rate IS NOT DISTINCT FROM {row}.rate AND id > $2))) ORDER BY rate DESC NULLS FIRST, id ASC NULLS LAST LIMIT $3",
+                row = row
+            )
+        );
+    }
+
+    #[test]
+    fn refuses_cursors_of_another_sort() {
+        let mut values = Map::new();
+        values.insert("rate".to_string(), json!("x"));
+
+        let mut query_builder = QueryBuilder::new("workouts");
+        query_builder.order_by(vec![Order::new("rate", Direction::Asc)]);
+
+        assert!(query_builder
+            .paginate(
+                10,
+                Some(&Cursor { id: 3, values }),
+                &[("rate", FieldKind::Int)]
+            )
+            .is_err());
+    }
+}
@@ -1,8 +1,23 @@
 use crate::query_builder::QueryBuilder;
 
+/// Type of a column, telling which values sorting and filtering accept for it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FieldKind {
+    Int,
+    Float,
+    Text,
+    Timestamp,
+}
+
 pub trait Queryable {
     fn fields() -> Vec<&'static str>;
 
+    /// Columns list requests may sort on.
+    fn sortable() -> Vec<(&'static str, FieldKind)>;
+
+    /// Columns list requests may filter on.
+    fn filterable() -> Vec<(&'static str, FieldKind)>;
+
     fn table() -> &'static str;
 
     fn query() -> QueryBuilder;
@@ -0,0 +1,29 @@
+use tonic::codegen::http;
+
+/// Called rpc, taken from the request path (`/santa_cruz.WorkoutService/GetWorkout`) by
+/// [`insert_rpc_method`] because interceptors only get the metadata and extensions.
+#[derive(Debug, Clone)]
+pub struct RpcMethod {
+    pub service: String,
+    pub method: String,
+}
+
+impl RpcMethod {
+    pub fn from_path(path: &str) -> Option<RpcMethod> {
+        let (service, method) = path.trim_start_matches('/').split_once('/')?;
+        let service = service.rsplit('.').next()?;
+
+        Some(RpcMethod {
+            service: service.to_string(),
+            method: method.to_string(),
+        })
+    }
+}
+
+pub fn insert_rpc_method<B>(mut request: http::Request<B>) -> http::Request<B> {
+    if let Some(rpc_method) = RpcMethod::from_path(request.uri().path()) {
+        request.extensions_mut().insert(rpc_method);
+    }
+
+    request
+}
@@ -1,6 +1,14 @@
+use crate::audit;
+use crate::coaching;
+use crate::errors;
+use crate::filtering;
 use crate::me_extension::MeExtension;
+use crate::pagination;
 use crate::proto::proto;
 use crate::query_builder::QueryBuilder;
+use crate::queryable::FieldKind;
+use crate::sharing;
+use crate::sorting;
 use crate::Queryable;
 use chrono::{DateTime, Utc};
 use sqlx::postgres::PgRow;
@@ -15,7 +23,10 @@ pub mod exercise {
     use super::*;
     use crate::proto::proto::santa_cruz::{
         CreateExerciseRequest, DeleteExerciseRequest, DeleteExerciseResponse, Exercise,
-        GetExerciseRequest, GetExercisesRequest, GetExercisesResponse, UpdateExerciseRequest,
+        GetExerciseRequest, GetExercisesRequest, GetExercisesResponse,
+        ListExerciseCollaboratorsRequest, ListExerciseCollaboratorsResponse, ShareExerciseRequest,
+        ShareExerciseResponse, UnshareExerciseRequest, UnshareExerciseResponse,
+        UpdateExerciseRequest,
     };
     impl From<PgRow> for Exercise {
         fn from(row: PgRow) -> Self {
@@ -32,6 +43,22 @@ pub mod exercise {
         fn fields() -> Vec<&'static str> {
             vec!["id", "created_at", "updated_at", "name", "description"]
         }
+        fn sortable() -> Vec<(&'static str, FieldKind)> {
+            vec![
+                ("created_at", FieldKind::Timestamp),
+                ("updated_at", FieldKind::Timestamp),
+                ("name", FieldKind::Text),
+            ]
+        }
+        fn filterable() -> Vec<(&'static str, FieldKind)> {
+            vec![
+                ("id", FieldKind::Int),
+                ("created_at", FieldKind::Timestamp),
+                ("updated_at", FieldKind::Timestamp),
+                ("name", FieldKind::Text),
+                ("description", FieldKind::Text),
+            ]
+        }
         fn table() -> &'static str {
             "exercises"
         }
@@ -50,7 +77,7 @@ pub mod exercise {
         }
         pub async fn get_exercise_by_id(pool: &PgPool, id: i32, user_id: i32) -> Option<Exercise> {
             let mut query_builder = Exercise::query();
-            query_builder . where_raw ("((permissions ->> CAST(${index} as text))::integer > 0 OR (permissions ->> '0')::integer > 0)" , user_id) ;
+            query_builder.where_permission(sharing::Level::Viewer, user_id);
             query_builder.where_eq("id", id);
             let sql = query_builder.select_query();
             sqlx::query_with(sql.0.as_str(), sql.1)
@@ -79,7 +106,7 @@ pub mod exercise {
             &self,
             request: Request<GetExerciseRequest>,
         ) -> Result<Response<Exercise>, Status> {
-            let MeExtension { user_id } = request.extensions().get::<MeExtension>().unwrap();
+            let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
             let GetExerciseRequest { id } = request.get_ref();
             self.return_exercise_by_id(*id, *user_id).await
         }
@@ -87,11 +114,12 @@ pub mod exercise {
             &self,
             request: Request<CreateExerciseRequest>,
         ) -> Result<Response<Exercise>, Status> {
-            let MeExtension { user_id } = request.extensions().get::<MeExtension>().unwrap();
+            let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
             let CreateExerciseRequest { name, description } = request.get_ref();
             let mut query_builder = Exercise::query();
+            let owner_id = *user_id;
             let mut permissions = HashMap::new();
-            permissions.insert(user_id, 2);
+            permissions.insert(owner_id, sharing::Level::Owner as i32);
             query_builder.field_with_argument("permissions", Json(permissions));
             query_builder.field_with_argument("name", name);
             query_builder.field_with_argument("description", description);
@@ -100,14 +128,15 @@ pub mod exercise {
                 .fetch_one(&self.pool)
                 .await
                 .expect("create error");
-            self.return_exercise_by_id(rec.get::<i32, _>("id"), *user_id)
-                .await
+            let id = rec.get::<i32, _>("id");
+            audit::record(&self.pool, request.extensions(), "exercises", id, None).await;
+            self.return_exercise_by_id(id, *user_id).await
         }
         async fn update_exercise(
             &self,
             request: Request<UpdateExerciseRequest>,
         ) -> Result<Response<Exercise>, Status> {
-            let MeExtension { user_id } = request.extensions().get::<MeExtension>().unwrap();
+            let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
             let UpdateExerciseRequest {
                 id,
                 name,
@@ -120,6 +149,20 @@ pub mod exercise {
                     id.to_string()
                 )));
             }
+            if !sharing::has_level(
+                &self.pool,
+                "exercises",
+                *id,
+                *user_id,
+                sharing::Level::Editor,
+            )
+            .await
+            {
+                return Err(Status::permission_denied(format!(
+                    "editor permission required for object #{}",
+                    id
+                )));
+            }
             let mut query_builder = Exercise::query();
             if let Some(name) = name {
                 query_builder.field_with_argument("name", name);
@@ -130,6 +173,7 @@ pub mod exercise {
             if !query_builder.has_fields() {
                 return Ok(Response::new(original.unwrap()));
             }
+            let before = audit::snapshot(&self.pool, "exercises", *id).await;
             query_builder.field_with_argument("updated_at", Utc::now());
             query_builder.where_eq("id", id);
             let sql = query_builder.update_query();
@@ -137,44 +181,133 @@ pub mod exercise {
                 .execute(&self.pool)
                 .await
                 .expect("update_workout_repeat error");
+            audit::record(&self.pool, request.extensions(), "exercises", *id, before).await;
             self.return_exercise_by_id(*id, *user_id).await
         }
         async fn delete_exercise(
             &self,
             request: Request<DeleteExerciseRequest>,
         ) -> Result<Response<DeleteExerciseResponse>, Status> {
-            let MeExtension { user_id } = &request.extensions().get::<MeExtension>().unwrap();
+            let MeExtension { user_id, .. } = &request.extensions().get::<MeExtension>().unwrap();
             let DeleteExerciseRequest { id } = request.get_ref();
             let mut query_builder = Exercise::query();
-            query_builder.where_raw(
-                "(permissions ->> CAST(${index} as text))::integer > 1",
-                user_id,
-            );
+            query_builder.where_permission(sharing::Level::Owner, *user_id);
             query_builder.where_eq("id", id);
             let sql = query_builder.delete_query();
-            sqlx::query_with(sql.0.as_str(), sql.1)
+            let before = audit::snapshot(&self.pool, "exercises", *id).await;
+            let result = sqlx::query_with(sql.0.as_str(), sql.1)
                 .execute(&self.pool)
                 .await
                 .expect("delete error");
+            if result.rows_affected() == 0 {
+                return Err(sharing::write_denied(
+                    &self.pool,
+                    "exercises",
+                    *id,
+                    *user_id,
+                    sharing::Level::Owner,
+                )
+                .await);
+            }
+            audit::record(&self.pool, request.extensions(), "exercises", *id, before).await;
             Ok(Response::new(DeleteExerciseResponse {}))
         }
         async fn get_exercises(
             &self,
             request: Request<GetExercisesRequest>,
         ) -> Result<Response<GetExercisesResponse>, Status> {
-            let MeExtension { user_id } = request.extensions().get::<MeExtension>().unwrap();
-            let GetExercisesRequest {} = request.get_ref();
+            let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+            let GetExercisesRequest {
+                page_size,
+                page_token,
+                sort,
+                filter,
+            } = request.get_ref();
+            let page_size = pagination::page_size(*page_size);
+            let cursor =
+                pagination::Cursor::decode(page_token).map_err(Status::invalid_argument)?;
+            let order =
+                sorting::parse(sort, &Exercise::sortable()).map_err(Status::invalid_argument)?;
             let mut query_builder = Exercise::query();
-            query_builder . where_raw ("((permissions ->> CAST(${index} as text))::integer > 0 OR (permissions ->> '0')::integer > 0)" , user_id) ;
+            query_builder.where_permission(sharing::Level::Viewer, *user_id);
+            filtering::apply(&mut query_builder, filter.as_ref(), &Exercise::filterable())
+                .map_err(Status::invalid_argument)?;
+            query_builder.order_by(order);
+            query_builder
+                .paginate(page_size, cursor.as_ref(), &Exercise::sortable())
+                .map_err(Status::invalid_argument)?;
             let sql = query_builder.select_query();
-            let exercises = sqlx::query_with(sql.0.as_str(), sql.1)
+            let mut rows = sqlx::query_with(sql.0.as_str(), sql.1)
                 .fetch_all(&self.pool)
                 .await
-                .expect("error")
-                .into_iter()
-                .map(|row| row.into())
-                .collect();
-            Ok(Response::new(GetExercisesResponse { exercises }))
+                .map_err(errors::internal)?;
+            let next_page_token =
+                pagination::next_page_token(&mut rows, page_size, pagination::row_cursor);
+            let exercises = rows.into_iter().map(|row| row.into()).collect();
+            Ok(Response::new(GetExercisesResponse {
+                exercises,
+                next_page_token,
+            }))
+        }
+        async fn share_exercise(
+            &self,
+            request: Request<ShareExerciseRequest>,
+        ) -> Result<Response<ShareExerciseResponse>, Status> {
+            let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+            let ShareExerciseRequest {
+                id,
+                email,
+                level,
+                group_id,
+            } = request.get_ref();
+            let before = audit::snapshot(&self.pool, "exercises", *id).await;
+            let collaborators = sharing::share(
+                &self.pool,
+                "exercises",
+                *id,
+                *user_id,
+                email,
+                *group_id,
+                *level,
+            )
+            .await?;
+            audit::record(&self.pool, request.extensions(), "exercises", *id, before).await;
+            Ok(Response::new(ShareExerciseResponse { collaborators }))
+        }
+        async fn unshare_exercise(
+            &self,
+            request: Request<UnshareExerciseRequest>,
+        ) -> Result<Response<UnshareExerciseResponse>, Status> {
+            let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+            let UnshareExerciseRequest {
+                id,
+                collaborator_id,
+                group_id,
+            } = request.get_ref();
+            let before = audit::snapshot(&self.pool, "exercises", *id).await;
+            let collaborators = sharing::unshare(
+                &self.pool,
+                "exercises",
+                *id,
+                *user_id,
+                *collaborator_id,
+                *group_id,
+            )
+            .await?;
+            audit::record(&self.pool, request.extensions(), "exercises", *id, before).await;
+            Ok(Response::new(UnshareExerciseResponse { collaborators }))
+        }
+        async fn list_exercise_collaborators(
+            &self,
+            request: Request<ListExerciseCollaboratorsRequest>,
+        ) -> Result<Response<ListExerciseCollaboratorsResponse>, Status> {
+            let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+            let ListExerciseCollaboratorsRequest { id } = request.get_ref();
+            let collaborators =
+                sharing::list_collaborators(&self.pool, "exercises", *id, *user_id).await?;
+            Ok(Response::new(ListExerciseCollaboratorsResponse {
+                collaborators,
+            }))
         }
     }
 }
@@ -182,7 +315,10 @@ pub mod workout {
     use super::*;
     use crate::proto::proto::santa_cruz::{
         CreateWorkoutRequest, DeleteWorkoutRequest, DeleteWorkoutResponse, GetWorkoutRequest,
-        GetWorkoutsRequest, GetWorkoutsResponse, UpdateWorkoutRequest, Workout, WorkoutStatus,
+        GetWorkoutTreeRequest, GetWorkoutsRequest, GetWorkoutsResponse,
+        ListWorkoutCollaboratorsRequest, ListWorkoutCollaboratorsResponse, ShareWorkoutRequest,
+        ShareWorkoutResponse, UnshareWorkoutRequest, UnshareWorkoutResponse, UpdateWorkoutRequest,
+        Workout, WorkoutStatus, WorkoutTree,
     };
     impl sqlx::Type<sqlx::Postgres> for WorkoutStatus {
         fn type_info() -> PgTypeInfo {
@@ -230,6 +366,26 @@ pub mod workout {
                 "rate",
             ]
         }
+        fn sortable() -> Vec<(&'static str, FieldKind)> {
+            vec![
+                ("created_at", FieldKind::Timestamp),
+                ("updated_at", FieldKind::Timestamp),
+                ("status", FieldKind::Text),
+                ("day", FieldKind::Timestamp),
+                ("rate", FieldKind::Int),
+            ]
+        }
+        fn filterable() -> Vec<(&'static str, FieldKind)> {
+            vec![
+                ("id", FieldKind::Int),
+                ("created_at", FieldKind::Timestamp),
+                ("updated_at", FieldKind::Timestamp),
+                ("status", FieldKind::Text),
+                ("day", FieldKind::Timestamp),
+                ("rate", FieldKind::Int),
+                ("comment", FieldKind::Text),
+            ]
+        }
         fn table() -> &'static str {
             "workouts"
         }
@@ -248,7 +404,7 @@ pub mod workout {
         }
         pub async fn get_workout_by_id(pool: &PgPool, id: i32, user_id: i32) -> Option<Workout> {
             let mut query_builder = Workout::query();
-            query_builder . where_raw ("((permissions ->> CAST(${index} as text))::integer > 0 OR (permissions ->> '0')::integer > 0)" , user_id) ;
+            query_builder.where_permission(sharing::Level::Viewer, user_id);
             query_builder.where_eq("id", id);
             let sql = query_builder.select_query();
             sqlx::query_with(sql.0.as_str(), sql.1)
@@ -277,7 +433,7 @@ pub mod workout {
             &self,
             request: Request<GetWorkoutRequest>,
         ) -> Result<Response<Workout>, Status> {
-            let MeExtension { user_id } = request.extensions().get::<MeExtension>().unwrap();
+            let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
             let GetWorkoutRequest { id } = request.get_ref();
             self.return_workout_by_id(*id, *user_id).await
         }
@@ -285,11 +441,18 @@ pub mod workout {
             &self,
             request: Request<CreateWorkoutRequest>,
         ) -> Result<Response<Workout>, Status> {
-            let MeExtension { user_id } = request.extensions().get::<MeExtension>().unwrap();
-            let CreateWorkoutRequest { status, day } = request.get_ref();
+            let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+            let CreateWorkoutRequest {
+                status,
+                day,
+                athlete_id,
+            } = request.get_ref();
             let mut query_builder = Workout::query();
+            let owner_id = coaching::resolve_owner(&self.pool, *user_id, *athlete_id)
+                .await
+                .map_err(Status::permission_denied)?;
             let mut permissions = HashMap::new();
-            permissions.insert(user_id, 2);
+            permissions.insert(owner_id, sharing::Level::Owner as i32);
             query_builder.field_with_argument("permissions", Json(permissions));
             query_builder.field_with_argument("status", status);
             query_builder.field_with_argument("day", day);
@@ -298,14 +461,15 @@ pub mod workout {
                 .fetch_one(&self.pool)
                 .await
                 .expect("create error");
-            self.return_workout_by_id(rec.get::<i32, _>("id"), *user_id)
-                .await
+            let id = rec.get::<i32, _>("id");
+            audit::record(&self.pool, request.extensions(), "workouts", id, None).await;
+            self.return_workout_by_id(id, *user_id).await
         }
         async fn update_workout(
             &self,
             request: Request<UpdateWorkoutRequest>,
         ) -> Result<Response<Workout>, Status> {
-            let MeExtension { user_id } = request.extensions().get::<MeExtension>().unwrap();
+            let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
             let UpdateWorkoutRequest {
                 id,
                 status,
@@ -320,6 +484,20 @@ pub mod workout {
                     id.to_string()
                 )));
             }
+            if !sharing::has_level(
+                &self.pool,
+                "workouts",
+                *id,
+                *user_id,
+                sharing::Level::Editor,
+            )
+            .await
+            {
+                return Err(Status::permission_denied(format!(
+                    "editor permission required for object #{}",
+                    id
+                )));
+            }
             let mut query_builder = Workout::query();
             if let Some(status) = status {
                 query_builder.field_with_argument("status", status);
@@ -336,6 +514,7 @@ pub mod workout {
             if !query_builder.has_fields() {
                 return Ok(Response::new(original.unwrap()));
             }
+            let before = audit::snapshot(&self.pool, "workouts", *id).await;
             query_builder.field_with_argument("updated_at", Utc::now());
             query_builder.where_eq("id", id);
             let sql = query_builder.update_query();
@@ -343,44 +522,137 @@ pub mod workout {
                 .execute(&self.pool)
                 .await
                 .expect("update_workout_repeat error");
+            audit::record(&self.pool, request.extensions(), "workouts", *id, before).await;
             self.return_workout_by_id(*id, *user_id).await
         }
         async fn delete_workout(
             &self,
             request: Request<DeleteWorkoutRequest>,
         ) -> Result<Response<DeleteWorkoutResponse>, Status> {
-            let MeExtension { user_id } = &request.extensions().get::<MeExtension>().unwrap();
+            let MeExtension { user_id, .. } = &request.extensions().get::<MeExtension>().unwrap();
             let DeleteWorkoutRequest { id } = request.get_ref();
             let mut query_builder = Workout::query();
-            query_builder.where_raw(
-                "(permissions ->> CAST(${index} as text))::integer > 1",
-                user_id,
-            );
+            query_builder.where_permission(sharing::Level::Owner, *user_id);
             query_builder.where_eq("id", id);
             let sql = query_builder.delete_query();
-            sqlx::query_with(sql.0.as_str(), sql.1)
+            let before = audit::snapshot(&self.pool, "workouts", *id).await;
+            let result = sqlx::query_with(sql.0.as_str(), sql.1)
                 .execute(&self.pool)
                 .await
                 .expect("delete error");
+            if result.rows_affected() == 0 {
+                return Err(sharing::write_denied(
+                    &self.pool,
+                    "workouts",
+                    *id,
+                    *user_id,
+                    sharing::Level::Owner,
+                )
+                .await);
+            }
+            audit::record(&self.pool, request.extensions(), "workouts", *id, before).await;
             Ok(Response::new(DeleteWorkoutResponse {}))
         }
         async fn get_workouts(
             &self,
             request: Request<GetWorkoutsRequest>,
         ) -> Result<Response<GetWorkoutsResponse>, Status> {
-            let MeExtension { user_id } = request.extensions().get::<MeExtension>().unwrap();
-            let GetWorkoutsRequest {} = request.get_ref();
+            let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+            let GetWorkoutsRequest {
+                page_size,
+                page_token,
+                sort,
+                filter,
+            } = request.get_ref();
+            let page_size = pagination::page_size(*page_size);
+            let cursor =
+                pagination::Cursor::decode(page_token).map_err(Status::invalid_argument)?;
+            let order =
+                sorting::parse(sort, &Workout::sortable()).map_err(Status::invalid_argument)?;
             let mut query_builder = Workout::query();
-            query_builder . where_raw ("((permissions ->> CAST(${index} as text))::integer > 0 OR (permissions ->> '0')::integer > 0)" , user_id) ;
+            query_builder.where_permission(sharing::Level::Viewer, *user_id);
+            filtering::apply(&mut query_builder, filter.as_ref(), &Workout::filterable())
+                .map_err(Status::invalid_argument)?;
+            query_builder.order_by(order);
+            query_builder
+                .paginate(page_size, cursor.as_ref(), &Workout::sortable())
+                .map_err(Status::invalid_argument)?;
             let sql = query_builder.select_query();
-            let workouts = sqlx::query_with(sql.0.as_str(), sql.1)
+            let mut rows = sqlx::query_with(sql.0.as_str(), sql.1)
                 .fetch_all(&self.pool)
                 .await
-                .expect("error")
-                .into_iter()
-                .map(|row| row.into())
-                .collect();
-            Ok(Response::new(GetWorkoutsResponse { workouts }))
+                .map_err(errors::internal)?;
+            let next_page_token =
+                pagination::next_page_token(&mut rows, page_size, pagination::row_cursor);
+            let workouts = rows.into_iter().map(|row| row.into()).collect();
+            Ok(Response::new(GetWorkoutsResponse {
+                workouts,
+                next_page_token,
+            }))
+        }
+        async fn share_workout(
+            &self,
+            request: Request<ShareWorkoutRequest>,
+        ) -> Result<Response<ShareWorkoutResponse>, Status> {
+            let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+            let ShareWorkoutRequest {
+                id,
+                email,
+                level,
+                group_id,
+            } = request.get_ref();
+            let before = audit::snapshot(&self.pool, "workouts", *id).await;
+            let collaborators = sharing::share(
+                &self.pool, "workouts", *id, *user_id, email, *group_id, *level,
+            )
+            .await?;
+            audit::record(&self.pool, request.extensions(), "workouts", *id, before).await;
+            Ok(Response::new(ShareWorkoutResponse { collaborators }))
+        }
+        async fn unshare_workout(
+            &self,
+            request: Request<UnshareWorkoutRequest>,
+        ) -> Result<Response<UnshareWorkoutResponse>, Status> {
+            let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+            let UnshareWorkoutRequest {
+                id,
+                collaborator_id,
+                group_id,
+            } = request.get_ref();
+            let before = audit::snapshot(&self.pool, "workouts", *id).await;
+            let collaborators = sharing::unshare(
+                &self.pool,
+                "workouts",
+                *id,
+                *user_id,
+                *collaborator_id,
+                *group_id,
+            )
+            .await?;
+            audit::record(&self.pool, request.extensions(), "workouts", *id, before).await;
+            Ok(Response::new(UnshareWorkoutResponse { collaborators }))
+        }
+        async fn list_workout_collaborators(
+            &self,
+            request: Request<ListWorkoutCollaboratorsRequest>,
+        ) -> Result<Response<ListWorkoutCollaboratorsResponse>, Status> {
+            let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+            let ListWorkoutCollaboratorsRequest { id } = request.get_ref();
+            let collaborators =
+                sharing::list_collaborators(&self.pool, "workouts", *id, *user_id).await?;
+            Ok(Response::new(ListWorkoutCollaboratorsResponse {
+                collaborators,
+            }))
+        }
+        async fn get_workout_tree(
+            &self,
+            request: Request<GetWorkoutTreeRequest>,
+        ) -> Result<Response<WorkoutTree>, Status> {
+            let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+            let GetWorkoutTreeRequest { id } = request.get_ref();
+            crate::workout_tree::get_workout_tree(&self.pool, *id, *user_id)
+                .await
+                .map(Response::new)
         }
     }
 }
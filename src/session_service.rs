@@ -1 +1,130 @@
+use std::sync::Arc;
 
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use tonic::{Request, Response, Status};
+
+use crate::audit;
+use crate::me_extension::MeExtension;
+use crate::proto::proto::santa_cruz;
+use crate::proto::proto::santa_cruz::{
+    DeleteSessionRequest, DeleteSessionResponse, GetSessionsRequest, GetSessionsResponse,
+};
+use crate::SessionsCache;
+
+pub struct SessionService {
+    pool: PgPool,
+    cache: Arc<SessionsCache>,
+}
+
+type SessionRow = (
+    i32,
+    DateTime<Utc>,
+    DateTime<Utc>,
+    i32,
+    String,
+    DateTime<Utc>,
+    Option<i32>,
+);
+
+impl From<SessionRow> for santa_cruz::Session {
+    fn from(row: SessionRow) -> Self {
+        santa_cruz::Session {
+            id: row.0,
+            created_at: row.1.to_rfc3339(),
+            updated_at: row.2.to_rfc3339(),
+            user_id: row.3,
+            device_name: row.4,
+            expires_at: row.5.to_rfc3339(),
+            impersonator_id: row.6.unwrap_or_default(),
+        }
+    }
+}
+
+impl SessionService {
+    pub fn new(pool: &PgPool, cache: Arc<SessionsCache>) -> SessionService {
+        SessionService {
+            pool: pool.clone(),
+            cache,
+        }
+    }
+}
+
+/// Deletes every session of the user but `keep`, returns how many there were. Their tokens are
+/// revoked in this instance's cache right away, the `revoked_tokens` NOTIFY only has to reach
+/// the other instances.
+pub async fn revoke_user_sessions(
+    pool: &PgPool,
+    cache: &SessionsCache,
+    user_id: i32,
+    keep: Option<&str>,
+) -> usize {
+    let tokens: Vec<(String, DateTime<Utc>)> = sqlx::query_as(
+        r#"DELETE FROM sessions WHERE user_id = $1 AND ($2::text IS NULL OR token <> $2) RETURNING token, expires_at"#,
+    )
+    .bind(user_id)
+    .bind(keep)
+    .fetch_all(pool)
+    .await
+    .expect("revoke_user_sessions error");
+
+    let count = tokens.len();
+
+    for (token, expires_at) in tokens {
+        cache.remove(token.clone());
+        cache.revoke(token, expires_at);
+    }
+
+    count
+}
+
+#[tonic::async_trait]
+impl santa_cruz::session_service_server::SessionService for SessionService {
+    async fn delete_session(
+        &self,
+        request: Request<DeleteSessionRequest>,
+    ) -> Result<Response<DeleteSessionResponse>, Status> {
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+        let DeleteSessionRequest { id } = request.get_ref();
+
+        let before = audit::snapshot(&self.pool, "sessions", *id).await;
+
+        let row: Option<(String, DateTime<Utc>)> = sqlx::query_as(
+            r#"DELETE FROM sessions WHERE id = $1 AND user_id = $2 RETURNING token, expires_at"#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .expect("delete_session error");
+
+        match row {
+            Some((token, expires_at)) => {
+                self.cache.remove(token.clone());
+                self.cache.revoke(token, expires_at);
+                audit::record(&self.pool, request.extensions(), "sessions", *id, before).await;
+                Ok(Response::new(DeleteSessionResponse {}))
+            }
+            None => Err(Status::not_found(format!("session #{} not found", id))),
+        }
+    }
+
+    async fn get_sessions(
+        &self,
+        request: Request<GetSessionsRequest>,
+    ) -> Result<Response<GetSessionsResponse>, Status> {
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+
+        let rows: Vec<SessionRow> = sqlx::query_as(
+            r#"SELECT id, created_at, updated_at, user_id, device_name, expires_at, impersonator_id FROM sessions WHERE user_id = $1 AND refresh_expires_at > NOW() ORDER BY created_at DESC"#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .expect("get_sessions error");
+
+        let sessions = rows.into_iter().map(|row| row.into()).collect();
+
+        Ok(Response::new(GetSessionsResponse { sessions }))
+    }
+}
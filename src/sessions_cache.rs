@@ -1,29 +1,122 @@
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
+use chrono::{DateTime, Utc};
 use log::debug;
 
+use crate::me_extension::{Role, Scope};
+
+#[derive(Debug, Clone, Copy)]
+pub struct CachedSession {
+    pub user_id: i32,
+    pub impersonator_id: Option<i32>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl CachedSession {
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= Utc::now()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CachedApiKey {
+    pub user_id: i32,
+    pub scopes: Vec<Scope>,
+}
+
 #[derive(Debug, Default)]
 pub struct SessionsCache {
-    cache: Arc<Mutex<HashMap<String, i32>>>,
+    cache: Arc<Mutex<HashMap<String, CachedSession>>>,
+    revoked: Arc<Mutex<HashMap<String, DateTime<Utc>>>>,
+    api_keys: Arc<Mutex<HashMap<String, CachedApiKey>>>,
+    /// Only users with another role than [`Role::User`] are kept.
+    roles: Arc<Mutex<HashMap<i32, Role>>>,
 }
 
 impl SessionsCache {
-    pub fn insert(&self, key: String, value: i32) {
-        debug!("added new session: {} - {}", key, value);
+    pub fn insert(&self, key: String, value: CachedSession) {
+        debug!(
+            "added new session: {} - {}",
+            value.user_id,
+            value.expires_at.to_rfc3339()
+        );
         let mut cache = self.cache.lock().unwrap();
         cache.insert(key, value);
     }
 
-    pub fn get(&self, key: String) -> Option<i32> {
-        match self.cache.lock().unwrap().get(&key) {
-            None => None,
-            Some(v) => Some(*v),
-        }
+    pub fn get(&self, key: String) -> Option<CachedSession> {
+        self.cache.lock().unwrap().get(&key).copied()
+    }
+
+    pub fn replace(&self, sessions: HashMap<String, CachedSession>) {
+        debug!("reloaded {} sessions", sessions.len());
+        let mut cache = self.cache.lock().unwrap();
+        *cache = sessions;
     }
 
     pub fn remove(&self, key: String) {
         let mut values = self.cache.lock().unwrap();
         values.remove(&key);
     }
+
+    pub fn revoke(&self, jti: String, expires_at: DateTime<Utc>) {
+        let mut revoked = self.revoked.lock().unwrap();
+        revoked.retain(|_, expires_at| *expires_at > Utc::now());
+        revoked.insert(jti, expires_at);
+    }
+
+    pub fn is_revoked(&self, jti: &str) -> bool {
+        self.revoked.lock().unwrap().contains_key(jti)
+    }
+
+    pub fn replace_revoked(&self, revoked: HashMap<String, DateTime<Utc>>) {
+        debug!("reloaded {} revoked tokens", revoked.len());
+        let mut values = self.revoked.lock().unwrap();
+        *values = revoked;
+    }
+
+    pub fn insert_api_key(&self, key_hash: String, value: CachedApiKey) {
+        let mut api_keys = self.api_keys.lock().unwrap();
+        api_keys.insert(key_hash, value);
+    }
+
+    pub fn get_api_key(&self, key_hash: &str) -> Option<CachedApiKey> {
+        self.api_keys.lock().unwrap().get(key_hash).cloned()
+    }
+
+    pub fn remove_api_key(&self, key_hash: &str) {
+        let mut api_keys = self.api_keys.lock().unwrap();
+        api_keys.remove(key_hash);
+    }
+
+    pub fn replace_api_keys(&self, api_keys: HashMap<String, CachedApiKey>) {
+        debug!("reloaded {} api keys", api_keys.len());
+        let mut values = self.api_keys.lock().unwrap();
+        *values = api_keys;
+    }
+
+    pub fn set_role(&self, user_id: i32, role: Role) {
+        let mut roles = self.roles.lock().unwrap();
+
+        match role {
+            Role::User => roles.remove(&user_id),
+            _ => roles.insert(user_id, role),
+        };
+    }
+
+    pub fn role(&self, user_id: i32) -> Role {
+        self.roles
+            .lock()
+            .unwrap()
+            .get(&user_id)
+            .copied()
+            .unwrap_or(Role::User)
+    }
+
+    pub fn replace_roles(&self, roles: HashMap<i32, Role>) {
+        debug!("reloaded {} roles", roles.len());
+        let mut values = self.roles.lock().unwrap();
+        *values = roles;
+    }
 }
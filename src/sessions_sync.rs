@@ -0,0 +1,200 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{TimeZone, Utc};
+use log::{debug, error, warn};
+use serde::Deserialize;
+use sqlx::postgres::PgListener;
+use sqlx::PgPool;
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+
+use crate::auth_interceptor::{fetch_api_keys, fetch_revoked, fetch_roles, fetch_sessions};
+use crate::me_extension::{Role, Scope};
+use crate::sessions_cache::{CachedApiKey, CachedSession};
+use crate::SessionsCache;
+
+const CHANNEL: &str = "sessions_changes";
+const RESYNC_INTERVAL_SECONDS: u64 = 5 * 60;
+const RECONNECT_DELAY_SECONDS: u64 = 5;
+
+/// Payload sent by the `sessions_changes` trigger.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum SessionChange {
+    Insert {
+        token: String,
+        user_id: i32,
+        impersonator_id: Option<i32>,
+        expires_at: i64,
+    },
+    Delete {
+        token: String,
+    },
+    Revoke {
+        jti: String,
+        expires_at: i64,
+    },
+    ApiKeyInsert {
+        key_hash: String,
+        user_id: i32,
+        scopes: Vec<String>,
+    },
+    ApiKeyDelete {
+        key_hash: String,
+    },
+    Role {
+        user_id: i32,
+        role: String,
+    },
+}
+
+enum SyncEvent {
+    Subscribed,
+    Change(String),
+}
+
+/// Keeps `cache` in sync with sessions written by other instances: changes are pushed
+/// through LISTEN/NOTIFY and the whole table is reloaded periodically in case some were missed.
+pub fn spawn_sessions_sync(pool: &PgPool, cache: Arc<SessionsCache>) {
+    let (sender, receiver) = mpsc::unbounded_channel();
+
+    tokio::spawn(listen(pool.clone(), sender));
+    tokio::spawn(sync(pool.clone(), cache, receiver));
+}
+
+async fn reload(pool: &PgPool, cache: &SessionsCache) {
+    match fetch_sessions(pool).await {
+        Ok(sessions) => cache.replace(sessions),
+        Err(e) => error!("sessions reload failed: {}", e),
+    }
+
+    match fetch_revoked(pool).await {
+        Ok(revoked) => cache.replace_revoked(revoked),
+        Err(e) => error!("revoked tokens reload failed: {}", e),
+    }
+
+    match fetch_api_keys(pool).await {
+        Ok(api_keys) => cache.replace_api_keys(api_keys),
+        Err(e) => error!("api keys reload failed: {}", e),
+    }
+
+    match fetch_roles(pool).await {
+        Ok(roles) => cache.replace_roles(roles),
+        Err(e) => error!("roles reload failed: {}", e),
+    }
+}
+
+fn apply(cache: &SessionsCache, payload: &str) {
+    match serde_json::from_str::<SessionChange>(payload) {
+        Ok(SessionChange::Insert {
+            token,
+            user_id,
+            impersonator_id,
+            expires_at,
+        }) => match Utc.timestamp_opt(expires_at, 0).single() {
+            Some(expires_at) => cache.insert(
+                token,
+                CachedSession {
+                    user_id,
+                    impersonator_id,
+                    expires_at,
+                },
+            ),
+            None => warn!("session change with invalid expiration: {}", expires_at),
+        },
+        Ok(SessionChange::Delete { token }) => cache.remove(token),
+        Ok(SessionChange::Revoke { jti, expires_at }) => {
+            match Utc.timestamp_opt(expires_at, 0).single() {
+                Some(expires_at) => cache.revoke(jti, expires_at),
+                None => warn!("revoked token with invalid expiration: {}", expires_at),
+            }
+        }
+        Ok(SessionChange::ApiKeyInsert {
+            key_hash,
+            user_id,
+            scopes,
+        }) => cache.insert_api_key(
+            key_hash,
+            CachedApiKey {
+                user_id,
+                scopes: scopes.iter().filter_map(|s| Scope::parse(s)).collect(),
+            },
+        ),
+        Ok(SessionChange::ApiKeyDelete { key_hash }) => cache.remove_api_key(&key_hash),
+        Ok(SessionChange::Role { user_id, role }) => match Role::parse(&role) {
+            Some(role) => cache.set_role(user_id, role),
+            None => warn!("unknown role {} for user {}", role, user_id),
+        },
+        Err(e) => warn!("unknown session change: {}", e),
+    }
+}
+
+async fn listen(pool: PgPool, sender: UnboundedSender<SyncEvent>) {
+    loop {
+        let mut listener = match PgListener::connect_with(&pool).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                error!("sessions listener connection failed: {}", e);
+                tokio::time::sleep(Duration::from_secs(RECONNECT_DELAY_SECONDS)).await;
+                continue;
+            }
+        };
+
+        if let Err(e) = listener.listen(CHANNEL).await {
+            error!("sessions listener subscription failed: {}", e);
+            tokio::time::sleep(Duration::from_secs(RECONNECT_DELAY_SECONDS)).await;
+            continue;
+        }
+
+        if sender.send(SyncEvent::Subscribed).is_err() {
+            return;
+        }
+
+        loop {
+            match listener.try_recv().await {
+                Ok(Some(notification)) => {
+                    let event = SyncEvent::Change(notification.payload().to_string());
+
+                    if sender.send(event).is_err() {
+                        return;
+                    }
+                }
+                Ok(None) => {
+                    warn!("sessions listener connection lost");
+                    break;
+                }
+                Err(e) => {
+                    error!("sessions listener failed: {}", e);
+                    break;
+                }
+            }
+        }
+
+        tokio::time::sleep(Duration::from_secs(RECONNECT_DELAY_SECONDS)).await;
+    }
+}
+
+/// Applies changes and reloads from a single task, so a reload can't overwrite
+/// a change that was received while it was running.
+async fn sync(pool: PgPool, cache: Arc<SessionsCache>, mut receiver: UnboundedReceiver<SyncEvent>) {
+    let mut interval = tokio::time::interval(Duration::from_secs(RESYNC_INTERVAL_SECONDS));
+
+    // the first tick completes immediately and the cache was just loaded
+    interval.tick().await;
+
+    loop {
+        tokio::select! {
+            event = receiver.recv() => match event {
+                // anything sent before the subscription was made is only visible in the table
+                Some(SyncEvent::Subscribed) => reload(&pool, &cache).await,
+                Some(SyncEvent::Change(payload)) => {
+                    debug!("session change received");
+                    apply(&cache, &payload);
+                }
+                None => return,
+            },
+            _ = interval.tick() => reload(&pool, &cache).await,
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use tonic::{Request, Response, Status};
+
+use crate::audit;
+use crate::auth_service::generate_token;
+use crate::me_extension::MeExtension;
+use crate::proto::proto::santa_cruz;
+use crate::proto::proto::santa_cruz::{
+    CreateWorkoutShareLinkRequest, DeleteWorkoutShareLinkRequest, DeleteWorkoutShareLinkResponse,
+    GetWorkoutShareLinksRequest, GetWorkoutShareLinksResponse, WorkoutShareLink,
+};
+use crate::sharing;
+use crate::sharing::Level;
+
+pub struct ShareLinkService {
+    pool: PgPool,
+}
+
+type ShareLinkRow = (i32, DateTime<Utc>, i32, String);
+
+impl From<ShareLinkRow> for WorkoutShareLink {
+    fn from(row: ShareLinkRow) -> Self {
+        WorkoutShareLink {
+            id: row.0,
+            created_at: row.1.to_rfc3339(),
+            workout_id: row.2,
+            slug: row.3,
+        }
+    }
+}
+
+impl ShareLinkService {
+    pub fn new(pool: &PgPool) -> ShareLinkService {
+        ShareLinkService { pool: pool.clone() }
+    }
+
+    /// Links make a workout readable by anyone, so they are managed by its owners only.
+    async fn require_owner(&self, workout_id: i32, user_id: i32) -> Result<(), String> {
+        match sharing::has_level(&self.pool, "workouts", workout_id, user_id, Level::Owner).await {
+            true => Ok(()),
+            false => Err(format!("workout #{} not found", workout_id)),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl santa_cruz::share_link_service_server::ShareLinkService for ShareLinkService {
+    async fn create_workout_share_link(
+        &self,
+        request: Request<CreateWorkoutShareLinkRequest>,
+    ) -> Result<Response<WorkoutShareLink>, Status> {
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+        let CreateWorkoutShareLinkRequest { workout_id } = request.get_ref();
+
+        self.require_owner(*workout_id, *user_id)
+            .await
+            .map_err(Status::not_found)?;
+
+        let row: ShareLinkRow = sqlx::query_as(
+            r#"INSERT INTO workout_share_links ( workout_id, user_id, slug ) VALUES ( $1, $2, $3 ) RETURNING id, created_at, workout_id, slug"#,
+        )
+        .bind(workout_id)
+        .bind(user_id)
+        .bind(generate_token())
+        .fetch_one(&self.pool)
+        .await
+        .expect("create_workout_share_link error");
+
+        audit::record(
+            &self.pool,
+            request.extensions(),
+            "workout_share_links",
+            row.0,
+            None,
+        )
+        .await;
+
+        Ok(Response::new(row.into()))
+    }
+
+    async fn delete_workout_share_link(
+        &self,
+        request: Request<DeleteWorkoutShareLinkRequest>,
+    ) -> Result<Response<DeleteWorkoutShareLinkResponse>, Status> {
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+        let DeleteWorkoutShareLinkRequest { id } = request.get_ref();
+
+        let row: Option<(i32,)> =
+            sqlx::query_as(r#"SELECT workout_id FROM workout_share_links WHERE id = $1"#)
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await
+                .expect("delete_workout_share_link error");
+
+        let not_found = || Status::not_found(format!("share link #{} not found", id));
+        let (workout_id,) = row.ok_or_else(not_found)?;
+
+        self.require_owner(workout_id, *user_id)
+            .await
+            .map_err(|_| not_found())?;
+
+        let before = audit::snapshot(&self.pool, "workout_share_links", *id).await;
+
+        sqlx::query(r#"DELETE FROM workout_share_links WHERE id = $1"#)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .expect("delete_workout_share_link error");
+
+        audit::record(
+            &self.pool,
+            request.extensions(),
+            "workout_share_links",
+            *id,
+            before,
+        )
+        .await;
+
+        Ok(Response::new(DeleteWorkoutShareLinkResponse {}))
+    }
+
+    async fn get_workout_share_links(
+        &self,
+        request: Request<GetWorkoutShareLinksRequest>,
+    ) -> Result<Response<GetWorkoutShareLinksResponse>, Status> {
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+        let GetWorkoutShareLinksRequest { workout_id } = request.get_ref();
+
+        self.require_owner(*workout_id, *user_id)
+            .await
+            .map_err(Status::not_found)?;
+
+        let rows: Vec<ShareLinkRow> = sqlx::query_as(
+            r#"SELECT id, created_at, workout_id, slug FROM workout_share_links WHERE workout_id = $1 ORDER BY created_at DESC"#,
+        )
+        .bind(workout_id)
+        .fetch_all(&self.pool)
+        .await
+        .expect("get_workout_share_links error");
+
+        let share_links = rows.into_iter().map(|row| row.into()).collect();
+
+        Ok(Response::new(GetWorkoutShareLinksResponse { share_links }))
+    }
+}
@@ -0,0 +1,496 @@
+use std::collections::HashMap;
+
+use sqlx::types::Json;
+use sqlx::{Executor, PgPool, Postgres, Transaction};
+use tonic::Status;
+
+use crate::auth_service::normalize_email;
+use crate::proto::proto::santa_cruz::{Collaborator, PermissionLevel};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Viewer = 1,
+    Editor = 2,
+    Owner = 3,
+}
+/// Key of the `permissions` map granting access to everyone.
+pub const PUBLIC_KEY: &str = "0";
+/// Prefix of the `permissions` keys granting access to the members of a group.
+pub const GROUP_PREFIX: &str = "g:";
+
+/// Tables whose rows copy the permissions of their parent: `(parent, child, foreign key)`.
+const INHERITED: &[(&str, &str, &str)] = &[
+    ("workouts", "workout_sets", "workout_id"),
+    ("workout_sets", "workout_repeats", "workout_set_id"),
+];
+
+/// Tables where accepted coaches get editor access to the rows their athletes own.
+const COACHED: &[&str] = &["workouts", "workout_sets", "workout_repeats"];
+
+pub type Permissions = HashMap<String, i32>;
+
+#[derive(Debug)]
+pub enum SharingError {
+    NotFound(i32),
+    NotOwner,
+    UserNotFound,
+    GroupNotFound(i32),
+    InvalidLevel(i32),
+    LastOwner,
+}
+
+impl From<SharingError> for Status {
+    fn from(error: SharingError) -> Self {
+        match error {
+            SharingError::NotFound(id) => Status::not_found(format!("object #{} not found", id)),
+            SharingError::NotOwner => {
+                Status::permission_denied("only owners can change permissions")
+            }
+            SharingError::UserNotFound => Status::not_found("user not found"),
+            SharingError::GroupNotFound(id) => {
+                Status::not_found(format!("group #{} not found", id))
+            }
+            SharingError::InvalidLevel(level) => {
+                Status::invalid_argument(format!("invalid permission level {}", level))
+            }
+            SharingError::LastOwner => {
+                Status::failed_precondition("the last owner can't be removed")
+            }
+        }
+    }
+}
+
+fn parse_level(level: i32) -> Result<Level, SharingError> {
+    match PermissionLevel::from_i32(level) {
+        Some(PermissionLevel::Viewer) => Ok(Level::Viewer),
+        Some(PermissionLevel::Editor) => Ok(Level::Editor),
+        Some(PermissionLevel::Owner) => Ok(Level::Owner),
+        _ => Err(SharingError::InvalidLevel(level)),
+    }
+}
+
+/// SQL condition requiring `level` on `table` from the user bound at `param` (`$2`, or
+/// through `QueryBuilder::where_permission`), either directly or through one of their groups. Public
+/// objects are open to viewers and the objects of an athlete to their coaches up to editors.
+///
+/// Checks go through the indexed `acl` table, which triggers keep in sync with `permissions`.
+/// The granted ids form one uncorrelated set, so Postgres reads them from `acl_principal_idx`
+/// once instead of probing `acl` for every row of `table`.
+pub fn permission_condition(table: &str, level: Level, param: &str) -> String {
+    let mut principals = vec![format!("CAST({} as text)", param)];
+
+    if level == Level::Viewer {
+        principals.push(format!("'{}'", PUBLIC_KEY));
+    }
+
+    let mut granted = vec![format!(
+        "SELECT acl.resource_id FROM acl WHERE acl.resource_type = '{}' AND acl.level >= {} AND acl.principal = ANY(ARRAY[{}] || ARRAY(SELECT '{}' || group_id FROM group_members WHERE user_id = {}))",
+        table,
+        level as i32,
+        principals.join(", "),
+        GROUP_PREFIX,
+        param
+    )];
+
+    if level <= Level::Editor && COACHED.contains(&table) {
+        granted.push(format!(
+            "SELECT acl.resource_id FROM acl JOIN coach_athletes ON acl.principal = CAST(coach_athletes.athlete_id as text) WHERE acl.resource_type = '{}' AND acl.level >= {} AND coach_athletes.coach_id = {} AND coach_athletes.accepted_at IS NOT NULL",
+            table,
+            Level::Owner as i32,
+            param
+        ));
+    }
+
+    format!("{}.id IN ({})", table, granted.join(" UNION "))
+}
+
+pub async fn has_level<'e, E>(executor: E, table: &str, id: i32, user_id: i32, level: Level) -> bool
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    sqlx::query(&format!(
+        "SELECT id FROM {} WHERE id = $1 AND {}",
+        table,
+        permission_condition(table, level, "$2")
+    ))
+    .bind(id)
+    .bind(user_id)
+    .fetch_optional(executor)
+    .await
+    .expect("has_level error")
+    .is_some()
+}
+
+/// Error of a write requiring `level` that matched no row of `table`: the row is either missing,
+/// hidden from `user_id`, or visible to them without `level`.
+pub async fn write_denied(
+    pool: &PgPool,
+    table: &str,
+    id: i32,
+    user_id: i32,
+    level: Level,
+) -> Status {
+    if !has_level(pool, table, id, user_id, Level::Viewer).await {
+        return Status::not_found(format!("object #{} not found", id));
+    }
+
+    let name = match level {
+        Level::Viewer => "viewer",
+        Level::Editor => "editor",
+        Level::Owner => "owner",
+    };
+
+    Status::permission_denied(format!("{} permission required for object #{}", name, id))
+}
+
+/// Permissions of the row when `user_id` has `level` on it. The row stays locked against
+/// `update_permissions` until `tx` ends, so children inserted meanwhile inherit what is current.
+pub async fn lock_permissions(
+    tx: &mut Transaction<'_, Postgres>,
+    table: &str,
+    id: i32,
+    user_id: i32,
+    level: Level,
+) -> Option<Permissions> {
+    let row: Option<(Json<Permissions>,)> = sqlx::query_as(&format!(
+        "SELECT permissions FROM {} WHERE id = $1 FOR SHARE",
+        table
+    ))
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await
+    .expect("lock_permissions error");
+
+    let permissions = row?.0 .0;
+
+    // checked once the lock is held, a cascade committed while waiting is visible by now
+    if !has_level(&mut *tx, table, id, user_id, level).await {
+        return None;
+    }
+
+    Some(permissions)
+}
+
+pub fn group_key(group_id: i32) -> String {
+    format!("{}{}", GROUP_PREFIX, group_id)
+}
+
+fn is_user_key(key: &str) -> bool {
+    key != PUBLIC_KEY && !key.starts_with(GROUP_PREFIX)
+}
+
+fn level_of(permissions: &Permissions, user_id: i32) -> i32 {
+    permissions
+        .get(&user_id.to_string())
+        .copied()
+        .unwrap_or_default()
+}
+
+pub async fn load_permissions(
+    pool: &PgPool,
+    table: &str,
+    id: i32,
+) -> Result<Permissions, SharingError> {
+    let row: Option<(Json<Permissions>,)> =
+        sqlx::query_as(&format!("SELECT permissions FROM {} WHERE id = $1", table))
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+            .expect("load_permissions error");
+
+    row.map(|row| row.0 .0).ok_or(SharingError::NotFound(id))
+}
+
+/// Applies `change` to the permissions of an object owned by `user_id`, the row stays
+/// locked meanwhile so concurrent changes can't remove every owner.
+async fn update_permissions(
+    pool: &PgPool,
+    table: &str,
+    id: i32,
+    user_id: i32,
+    change: impl FnOnce(&mut Permissions),
+) -> Result<(), SharingError> {
+    let mut tx = pool.begin().await.expect("update_permissions error");
+
+    let row: Option<(Json<Permissions>,)> = sqlx::query_as(&format!(
+        "SELECT permissions FROM {} WHERE id = $1 FOR UPDATE",
+        table
+    ))
+    .bind(id)
+    .fetch_optional(&mut tx)
+    .await
+    .expect("update_permissions error");
+
+    let mut permissions = row.map(|row| row.0 .0).ok_or(SharingError::NotFound(id))?;
+
+    match level_of(&permissions, user_id) {
+        0 => return Err(SharingError::NotFound(id)),
+        level if level >= Level::Owner as i32 => {}
+        _ => return Err(SharingError::NotOwner),
+    }
+
+    change(&mut permissions);
+
+    if !permissions
+        .iter()
+        .any(|(key, level)| is_user_key(key) && *level >= Level::Owner as i32)
+    {
+        return Err(SharingError::LastOwner);
+    }
+
+    sqlx::query(&format!(
+        "UPDATE {} SET permissions = $1, updated_at = NOW() WHERE id = $2",
+        table
+    ))
+    .bind(Json(&permissions))
+    .bind(id)
+    .execute(&mut tx)
+    .await
+    .expect("update_permissions error");
+
+    cascade_permissions(&mut tx, table, id, &permissions).await;
+
+    tx.commit().await.expect("update_permissions error");
+
+    Ok(())
+}
+
+/// Copies `permissions` to every descendant of the row, level by level.
+async fn cascade_permissions(
+    tx: &mut Transaction<'_, Postgres>,
+    table: &str,
+    id: i32,
+    permissions: &Permissions,
+) {
+    let mut parents = vec![(table, vec![id])];
+
+    while let Some((parent, ids)) = parents.pop() {
+        for (_, child, column) in INHERITED.iter().filter(|(table, ..)| *table == parent) {
+            let rows: Vec<(i32,)> = sqlx::query_as(&format!(
+                "UPDATE {} SET permissions = $1, updated_at = NOW() WHERE {} = ANY($2) RETURNING id",
+                child, column
+            ))
+            .bind(Json(permissions))
+            .bind(&ids)
+            .fetch_all(&mut *tx)
+            .await
+            .expect("cascade_permissions error");
+
+            if !rows.is_empty() {
+                parents.push((child, rows.into_iter().map(|row| row.0).collect()));
+            }
+        }
+    }
+}
+
+fn group_ids(permissions: &Permissions) -> Vec<i32> {
+    permissions
+        .keys()
+        .filter_map(|key| key.strip_prefix(GROUP_PREFIX))
+        .filter_map(|id| id.parse().ok())
+        .collect()
+}
+
+/// Whether the object was shared with the user, directly or through one of their groups.
+async fn is_shared_with(pool: &PgPool, permissions: &Permissions, user_id: i32) -> bool {
+    if level_of(permissions, user_id) > 0 {
+        return true;
+    }
+
+    sqlx::query(r#"SELECT id FROM group_members WHERE user_id = $1 AND group_id = ANY($2)"#)
+        .bind(user_id)
+        .bind(group_ids(permissions))
+        .fetch_optional(pool)
+        .await
+        .expect("is_shared_with error")
+        .is_some()
+}
+
+/// Whether `user_id` coaches an owner of a row of a `COACHED` table, which lets them view it,
+/// see `permission_condition`.
+async fn is_coaching_owner(
+    pool: &PgPool,
+    table: &str,
+    permissions: &Permissions,
+    user_id: i32,
+) -> bool {
+    if !COACHED.contains(&table) {
+        return false;
+    }
+
+    let owner_ids: Vec<i32> = permissions
+        .iter()
+        .filter(|(key, level)| is_user_key(key) && **level >= Level::Owner as i32)
+        .filter_map(|(key, _)| key.parse().ok())
+        .collect();
+
+    sqlx::query(
+        r#"SELECT id FROM coach_athletes WHERE coach_id = $1 AND athlete_id = ANY($2) AND accepted_at IS NOT NULL"#,
+    )
+    .bind(user_id)
+    .bind(owner_ids)
+    .fetch_optional(pool)
+    .await
+    .expect("is_coaching_owner error")
+    .is_some()
+}
+
+/// Users and groups listed in the permissions of an object, visible to anyone it is shared with
+/// and to the coaches of its owners.
+pub async fn list_collaborators(
+    pool: &PgPool,
+    table: &str,
+    id: i32,
+    user_id: i32,
+) -> Result<Vec<Collaborator>, SharingError> {
+    let permissions = load_permissions(pool, table, id).await?;
+
+    if !is_shared_with(pool, &permissions, user_id).await
+        && !is_coaching_owner(pool, table, &permissions, user_id).await
+    {
+        return Err(SharingError::NotFound(id));
+    }
+
+    let ids: Vec<i32> = permissions
+        .keys()
+        .filter(|key| is_user_key(key))
+        .filter_map(|key| key.parse().ok())
+        .collect();
+
+    let users: Vec<(i32, String)> =
+        sqlx::query_as(r#"SELECT id, email FROM users WHERE id = ANY($1) ORDER BY email"#)
+            .bind(ids)
+            .fetch_all(pool)
+            .await
+            .expect("list_collaborators error");
+
+    let groups: Vec<(i32, String)> =
+        sqlx::query_as(r#"SELECT id, name FROM groups WHERE id = ANY($1) ORDER BY name"#)
+            .bind(group_ids(&permissions))
+            .fetch_all(pool)
+            .await
+            .expect("list_collaborators error");
+
+    let users = users.into_iter().map(|(id, email)| Collaborator {
+        user_id: id,
+        email,
+        level: level_of(&permissions, id),
+        ..Collaborator::default()
+    });
+
+    let groups = groups.into_iter().map(|(id, name)| Collaborator {
+        level: permissions.get(&group_key(id)).copied().unwrap_or_default(),
+        group_id: id,
+        group_name: name,
+        ..Collaborator::default()
+    });
+
+    Ok(users.chain(groups).collect())
+}
+
+/// Grants `level` to the user with `email`, or to the members of `group_id` when set.
+pub async fn share(
+    pool: &PgPool,
+    table: &str,
+    id: i32,
+    user_id: i32,
+    email: &str,
+    group_id: Option<i32>,
+    level: i32,
+) -> Result<Vec<Collaborator>, SharingError> {
+    let level = parse_level(level)?;
+
+    let key = match group_id {
+        Some(group_id) => {
+            // groups can't own objects, owners stay accountable individuals
+            if level == Level::Owner {
+                return Err(SharingError::InvalidLevel(level as i32));
+            }
+
+            let member: Option<(i32,)> = sqlx::query_as(
+                r#"SELECT id FROM group_members WHERE group_id = $1 AND user_id = $2"#,
+            )
+            .bind(group_id)
+            .bind(user_id)
+            .fetch_optional(pool)
+            .await
+            .expect("share error");
+
+            member.ok_or(SharingError::GroupNotFound(group_id))?;
+
+            group_key(group_id)
+        }
+        None => {
+            let target: Option<(i32,)> =
+                sqlx::query_as(r#"SELECT id FROM users WHERE lower(email) = $1"#)
+                    .bind(normalize_email(email))
+                    .fetch_optional(pool)
+                    .await
+                    .expect("share error");
+
+            let (target_id,) = target.ok_or(SharingError::UserNotFound)?;
+
+            target_id.to_string()
+        }
+    };
+
+    update_permissions(pool, table, id, user_id, |permissions| {
+        permissions.insert(key, level as i32);
+    })
+    .await?;
+
+    list_collaborators(pool, table, id, user_id).await
+}
+
+/// Removes `collaborator_id`, or the group `group_id` when set, from the permissions.
+pub async fn unshare(
+    pool: &PgPool,
+    table: &str,
+    id: i32,
+    user_id: i32,
+    collaborator_id: i32,
+    group_id: Option<i32>,
+) -> Result<Vec<Collaborator>, SharingError> {
+    let key = match group_id {
+        Some(group_id) => group_key(group_id),
+        None => collaborator_id.to_string(),
+    };
+
+    update_permissions(pool, table, id, user_id, |permissions| {
+        permissions.remove(&key);
+    })
+    .await?;
+
+    // owners can remove themselves as long as another owner is left
+    if group_id.is_none() && collaborator_id == user_id {
+        return Ok(vec![]);
+    }
+
+    list_collaborators(pool, table, id, user_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grants_viewers_through_users_groups_public_and_coaches() {
+        assert_eq!(
+            permission_condition("workouts", Level::Viewer, "$2"),
+            "workouts.id IN (\
+                SELECT acl.resource_id FROM acl WHERE acl.resource_type = 'workouts' AND acl.level >= 1 AND acl.principal = ANY(ARRAY[CAST($2 as text), '0'] || ARRAY(SELECT 'g:' || group_id FROM group_members WHERE user_id = $2)) \
+                UNION \
+                SELECT acl.resource_id FROM acl JOIN coach_athletes ON acl.principal = CAST(coach_athletes.athlete_id as text) WHERE acl.resource_type = 'workouts' AND acl.level >= 3 AND coach_athletes.coach_id = $2 AND coach_athletes.accepted_at IS NOT NULL\
+            )"
+        );
+    }
+
+    #[test]
+    fn grants_owners_through_users_and_groups_only() {
+        assert_eq!(
+            permission_condition("workouts", Level::Owner, "$1"),
+            "workouts.id IN (SELECT acl.resource_id FROM acl WHERE acl.resource_type = 'workouts' AND acl.level >= 3 AND acl.principal = ANY(ARRAY[CAST($1 as text)] || ARRAY(SELECT 'g:' || group_id FROM group_members WHERE user_id = $1)))"
+        );
+        assert!(!permission_condition("exercises", Level::Editor, "$1").contains("coach_athletes"));
+    }
+}
@@ -0,0 +1,184 @@
+use crate::proto::proto::santa_cruz::{Sort, SortDirection, SortNulls};
+use crate::queryable::FieldKind;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone)]
+pub struct Order {
+    pub field: String,
+    pub direction: Direction,
+    pub nulls_first: bool,
+}
+
+impl Order {
+    pub fn new<F>(field: F, direction: Direction) -> Order
+    where
+        F: Into<String>,
+    {
+        Order {
+            field: field.into(),
+            direction,
+            nulls_first: direction == Direction::Desc,
+        }
+    }
+
+    pub fn to_sql(&self) -> String {
+        format!(
+            "{} {} NULLS {}",
+            self.field,
+            match self.direction {
+                Direction::Asc => "ASC",
+                Direction::Desc => "DESC",
+            },
+            match self.nulls_first {
+                true => "FIRST",
+                false => "LAST",
+            }
+        )
+    }
+
+    /// Condition keeping the rows sorted after `value`, nulls included.
+    pub fn after_sql(&self, value: &str) -> String {
+        let operator = match self.direction {
+            Direction::Asc => ">",
+            Direction::Desc => "<",
+        };
+
+        let nulls = match self.nulls_first {
+            true => format!("{} IS NULL AND {} IS NOT NULL", value, self.field),
+            false => format!("{} IS NULL AND {} IS NOT NULL", self.field, value),
+        };
+
+        format!(
+            "({field} {operator} {value} OR ({nulls}))",
+            field = self.field,
+            operator = operator,
+            value = value,
+            nulls = nulls
+        )
+    }
+}
+
+/// Checks the requested sort against the `sortable` columns of the listed type.
+pub fn parse(sort: &[Sort], sortable: &[(&str, FieldKind)]) -> Result<Vec<Order>, String> {
+    let mut orders: Vec<Order> = vec![];
+
+    for Sort {
+        field,
+        direction,
+        nulls,
+    } in sort
+    {
+        if !sortable.iter().any(|(sortable, _)| sortable == field) {
+            return Err(format!("can't sort by {}", field));
+        }
+
+        if orders.iter().any(|order| &order.field == field) {
+            return Err(format!("{} is sorted more than once", field));
+        }
+
+        let direction = match SortDirection::from_i32(*direction) {
+            Some(SortDirection::Desc) => Direction::Desc,
+            _ => Direction::Asc,
+        };
+
+        let mut order = Order::new(field, direction);
+
+        match SortNulls::from_i32(*nulls) {
+            Some(SortNulls::First) => order.nulls_first = true,
+            Some(SortNulls::Last) => order.nulls_first = false,
+            _ => {}
+        }
+
+        orders.push(order);
+    }
+
+    Ok(orders)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SORTABLE: &[(&str, FieldKind)] = &[
+        ("name", FieldKind::Text),
+        ("created_at", FieldKind::Timestamp),
+    ];
+
+    fn sort(field: &str, direction: SortDirection, nulls: SortNulls) -> Sort {
+        Sort {
+            field: field.to_string(),
+            direction: direction as i32,
+            nulls: nulls as i32,
+        }
+    }
+
+    #[test]
+    fn nulls_follow_the_direction_unless_requested() {
+        let orders = parse(
+            &[
+                sort("name", SortDirection::Unspecified, SortNulls::Unspecified),
+                sort("created_at", SortDirection::Desc, SortNulls::Unspecified),
+            ],
+            SORTABLE,
+        )
+        .unwrap();
+
+        assert_eq!(orders[0].to_sql(), "name ASC NULLS LAST");
+        assert_eq!(orders[1].to_sql(), "created_at DESC NULLS FIRST");
+
+        let orders = parse(
+            &[
+                sort("name", SortDirection::Asc, SortNulls::First),
+                sort("created_at", SortDirection::Desc, SortNulls::Last),
+            ],
+            SORTABLE,
+        )
+        .unwrap();
+
+        assert_eq!(orders[0].to_sql(), "name ASC NULLS FIRST");
+        assert_eq!(orders[1].to_sql(), "created_at DESC NULLS LAST");
+    }
+
+    #[test]
+    fn rejects_unknown_and_repeated_fields() {
+        let unknown = sort("permissions", SortDirection::Asc, SortNulls::Unspecified);
+        let name = sort("name", SortDirection::Asc, SortNulls::Unspecified);
+
+        assert_eq!(
+            parse(&[unknown], SORTABLE).unwrap_err(),
+            "can't sort by permissions"
+        );
+        assert_eq!(
+            parse(&[name.clone(), name], SORTABLE).unwrap_err(),
+            "name is sorted more than once"
+        );
+        assert!(parse(&[], SORTABLE).unwrap().is_empty());
+    }
+
+    #[test]
+    fn after_sql_places_nulls_like_the_order() {
+        let mut order = Order::new("rate", Direction::Asc);
+
+        assert_eq!(
+            order.after_sql("$1"),
+            "(rate > $1 OR (rate IS NULL AND $1 IS NOT NULL))"
+        );
+
+        order.nulls_first = true;
+
+        assert_eq!(
+            order.after_sql("$1"),
+            "(rate > $1 OR ($1 IS NULL AND rate IS NOT NULL))"
+        );
+
+        assert_eq!(
+            Order::new("rate", Direction::Desc).after_sql("$1"),
+            "(rate < $1 OR ($1 IS NULL AND rate IS NOT NULL))"
+        );
+    }
+}
@@ -1,13 +1,28 @@
+use std::sync::Arc;
+
 use chrono::{DateTime, Utc};
 use sqlx::PgPool;
 use tonic::{Request, Response, Status};
 
+use crate::audit;
+use crate::auth_service::{
+    expire_password_resets, hash_password, validate_password, verify_password,
+};
 use crate::me_extension::MeExtension;
 use crate::proto::proto::santa_cruz;
-use crate::proto::proto::santa_cruz::{MeRequest, User};
+use crate::proto::proto::santa_cruz::{
+    ChangePasswordRequest, ChangePasswordResponse, ConfirmTwoFactorRequest,
+    ConfirmTwoFactorResponse, EnrollTwoFactorRequest, EnrollTwoFactorResponse, MeRequest, User,
+};
+use crate::session_service::revoke_user_sessions;
+use crate::totp;
+use crate::SessionsCache;
+
+const RECOVERY_CODES_COUNT: usize = 10;
 
 pub struct UserService {
     pool: PgPool,
+    cache: Arc<SessionsCache>,
 }
 
 type UserRow = (i32, DateTime<Utc>, DateTime<Utc>, String);
@@ -35,8 +50,11 @@ impl Into<santa_cruz::User> for UserRow {
 }
 
 impl UserService {
-    pub fn new(pool: &PgPool) -> UserService {
-        UserService { pool: pool.clone() }
+    pub fn new(pool: &PgPool, cache: Arc<SessionsCache>) -> UserService {
+        UserService {
+            pool: pool.clone(),
+            cache,
+        }
     }
 }
 
@@ -48,4 +66,149 @@ impl santa_cruz::user_service_server::UserService for UserService {
         let reply = get_user_by_id(&self.pool, extension.user_id).await;
         Ok(Response::new(reply))
     }
+
+    async fn change_password(
+        &self,
+        request: Request<ChangePasswordRequest>,
+    ) -> Result<Response<ChangePasswordResponse>, Status> {
+        let MeExtension {
+            user_id,
+            session_token,
+            ..
+        } = request.extensions().get::<MeExtension>().unwrap();
+        let ChangePasswordRequest {
+            current_password,
+            new_password,
+        } = request.get_ref();
+
+        let (password,): (String,) = sqlx::query_as(r#"SELECT password FROM users WHERE id = $1"#)
+            .bind(user_id)
+            .fetch_one(&self.pool)
+            .await
+            .expect("change_password error");
+
+        if verify_password(current_password.to_string(), password).is_err() {
+            return Err(Status::permission_denied("current password is not valid"));
+        }
+
+        validate_password(new_password).map_err(Status::invalid_argument)?;
+
+        let before = audit::snapshot(&self.pool, "users", *user_id).await;
+
+        sqlx::query(r#"UPDATE users SET password = $1, updated_at = NOW() WHERE id = $2"#)
+            .bind(hash_password(new_password.to_string()).unwrap())
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .expect("change_password error");
+
+        expire_password_resets(&self.pool, *user_id).await;
+        revoke_user_sessions(&self.pool, &self.cache, *user_id, session_token.as_deref()).await;
+
+        audit::record(&self.pool, request.extensions(), "users", *user_id, before).await;
+
+        Ok(Response::new(ChangePasswordResponse {}))
+    }
+
+    async fn enroll_two_factor(
+        &self,
+        request: Request<EnrollTwoFactorRequest>,
+    ) -> Result<Response<EnrollTwoFactorResponse>, Status> {
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+
+        let secret = totp::generate_secret();
+
+        let before = audit::snapshot(&self.pool, "users", *user_id).await;
+
+        let row: Option<(String,)> = sqlx::query_as(
+            r#"UPDATE users SET totp_secret = $1, updated_at = NOW() WHERE id = $2 AND NOT totp_enabled RETURNING email"#,
+        )
+        .bind(&secret)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .expect("enroll_two_factor error");
+
+        if row.is_none() {
+            return Err(Status::failed_precondition(
+                "two-factor authentication is already enabled",
+            ));
+        }
+
+        let (email,) = row.unwrap();
+
+        audit::record(&self.pool, request.extensions(), "users", *user_id, before).await;
+
+        Ok(Response::new(EnrollTwoFactorResponse {
+            provisioning_uri: totp::provisioning_uri(&secret, &email),
+            secret,
+        }))
+    }
+
+    async fn confirm_two_factor(
+        &self,
+        request: Request<ConfirmTwoFactorRequest>,
+    ) -> Result<Response<ConfirmTwoFactorResponse>, Status> {
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+        let ConfirmTwoFactorRequest { code } = request.get_ref();
+
+        let (secret, enabled): (Option<String>, bool) =
+            sqlx::query_as(r#"SELECT totp_secret, totp_enabled FROM users WHERE id = $1"#)
+                .bind(user_id)
+                .fetch_one(&self.pool)
+                .await
+                .expect("confirm_two_factor error");
+
+        if enabled {
+            return Err(Status::failed_precondition(
+                "two-factor authentication is already enabled",
+            ));
+        }
+
+        if secret.is_none() {
+            return Err(Status::failed_precondition(
+                "two-factor enrollment was not started",
+            ));
+        }
+
+        let step = totp::verify(&secret.unwrap(), code, Utc::now().timestamp());
+
+        if step.is_none() {
+            return Err(Status::invalid_argument("code is not valid"));
+        }
+
+        let before = audit::snapshot(&self.pool, "users", *user_id).await;
+
+        sqlx::query(
+            r#"UPDATE users SET totp_enabled = TRUE, totp_last_step = $1, updated_at = NOW() WHERE id = $2"#,
+        )
+        .bind(step.unwrap())
+        .bind(user_id)
+        .execute(&self.pool)
+        .await
+        .expect("confirm_two_factor error");
+
+        audit::record(&self.pool, request.extensions(), "users", *user_id, before).await;
+
+        sqlx::query(r#"DELETE FROM recovery_codes WHERE user_id = $1"#)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .expect("confirm_two_factor error");
+
+        let recovery_codes = (0..RECOVERY_CODES_COUNT)
+            .map(|_| totp::generate_recovery_code())
+            .collect::<Vec<_>>();
+
+        for recovery_code in &recovery_codes {
+            sqlx::query(r#"INSERT INTO recovery_codes ( user_id, code ) VALUES ( $1 , $2 )"#)
+                .bind(user_id)
+                .bind(hash_password(recovery_code.to_string()).unwrap())
+                .execute(&self.pool)
+                .await
+                .expect("confirm_two_factor error");
+        }
+
+        Ok(Response::new(ConfirmTwoFactorResponse { recovery_codes }))
+    }
 }
@@ -1,12 +1,14 @@
-use std::collections::HashMap;
-
 use chrono::{DateTime, Utc};
 use sqlx::postgres::PgRow;
 use sqlx::types::Json;
 use sqlx::{PgPool, Row};
 use tonic::{Request, Response, Status};
 
+use crate::audit;
+use crate::errors;
+use crate::filtering;
 use crate::me_extension::MeExtension;
+use crate::pagination;
 use crate::proto::proto;
 use crate::proto::proto::santa_cruz::id_query::Value;
 use crate::proto::proto::santa_cruz::{
@@ -15,7 +17,29 @@ use crate::proto::proto::santa_cruz::{
     UpdateWorkoutRepeatRequest, WorkoutRepeat,
 };
 use crate::query_builder::QueryBuilder;
-use crate::WorkoutSetService;
+use crate::queryable::FieldKind;
+use crate::sharing;
+use crate::sharing::Level;
+use crate::sorting;
+
+const SORTABLE: &[(&str, FieldKind)] = &[
+    ("created_at", FieldKind::Timestamp),
+    ("updated_at", FieldKind::Timestamp),
+    ("repeats", FieldKind::Int),
+    ("weight", FieldKind::Float),
+    ("time", FieldKind::Float),
+];
+
+const FILTERABLE: &[(&str, FieldKind)] = &[
+    ("id", FieldKind::Int),
+    ("created_at", FieldKind::Timestamp),
+    ("updated_at", FieldKind::Timestamp),
+    ("workout_set_id", FieldKind::Int),
+    ("exercise_id", FieldKind::Int),
+    ("repeats", FieldKind::Int),
+    ("weight", FieldKind::Float),
+    ("time", FieldKind::Float),
+];
 
 pub struct WorkoutRepeatService {
     pool: PgPool,
@@ -57,7 +81,7 @@ impl WorkoutRepeatService {
             "weight",
             "time",
         ]);
-        query_builder.where_raw("((permissions ->> CAST(${index} as text))::integer > 0 OR (permissions ->> '0')::integer > 0)", user_id);
+        query_builder.where_permission(Level::Viewer, user_id);
         query_builder.where_eq("id", id);
 
         let sql = query_builder.select_query();
@@ -92,7 +116,7 @@ impl proto::santa_cruz::workout_repeat_service_server::WorkoutRepeatService
         &self,
         request: Request<GetWorkoutRepeatRequest>,
     ) -> Result<Response<WorkoutRepeat>, Status> {
-        let MeExtension { user_id } = request.extensions().get::<MeExtension>().unwrap();
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
         let GetWorkoutRepeatRequest { id } = request.get_ref();
 
         self.return_workout_repeat_by_id(*id, *user_id).await
@@ -102,7 +126,7 @@ impl proto::santa_cruz::workout_repeat_service_server::WorkoutRepeatService
         &self,
         request: Request<CreateWorkoutRepeatRequest>,
     ) -> Result<Response<WorkoutRepeat>, Status> {
-        let MeExtension { user_id } = request.extensions().get::<MeExtension>().unwrap();
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
 
         let CreateWorkoutRepeatRequest {
             workout_set_id,
@@ -112,23 +136,31 @@ impl proto::santa_cruz::workout_repeat_service_server::WorkoutRepeatService
             time,
         } = request.get_ref();
 
-        let workout_set =
-            WorkoutSetService::get_workout_set_by_id(&self.pool, *workout_set_id, *user_id).await;
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .expect("create_workout_repeat error");
 
-        if workout_set.is_none() {
-            return Err(Status::permission_denied(format!(
+        let permissions = sharing::lock_permissions(
+            &mut tx,
+            "workout_sets",
+            *workout_set_id,
+            *user_id,
+            Level::Editor,
+        )
+        .await
+        .ok_or_else(|| {
+            Status::permission_denied(format!(
                 "permissions not found for workout_set #{}",
-                workout_set_id.to_string()
-            )));
-        }
+                workout_set_id
+            ))
+        })?;
 
         let mut query_builder = QueryBuilder::new("workout_repeats");
 
         query_builder.field_with_argument("workout_set_id", workout_set_id);
         query_builder.field_with_argument("exercise_id", exercise_id);
-
-        let mut permissions = HashMap::new();
-        permissions.insert(user_id, 2);
         query_builder.field_with_argument("permissions", Json(permissions));
 
         if let Some(repeats) = repeats {
@@ -146,19 +178,31 @@ impl proto::santa_cruz::workout_repeat_service_server::WorkoutRepeatService
         let sql = query_builder.insert_query();
 
         let rec = sqlx::query_with(sql.0.as_str(), sql.1)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut tx)
             .await
             .expect("create_workout_repeat error");
 
-        self.return_workout_repeat_by_id(rec.get::<i32, _>("id"), *user_id)
-            .await
+        tx.commit().await.expect("create_workout_repeat error");
+
+        let id = rec.get::<i32, _>("id");
+
+        audit::record(
+            &self.pool,
+            request.extensions(),
+            "workout_repeats",
+            id,
+            None,
+        )
+        .await;
+
+        self.return_workout_repeat_by_id(id, *user_id).await
     }
 
     async fn update_workout_repeat(
         &self,
         request: Request<UpdateWorkoutRepeatRequest>,
     ) -> Result<Response<WorkoutRepeat>, Status> {
-        let MeExtension { user_id } = request.extensions().get::<MeExtension>().unwrap();
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
 
         let UpdateWorkoutRepeatRequest {
             id,
@@ -177,6 +221,13 @@ impl proto::santa_cruz::workout_repeat_service_server::WorkoutRepeatService
             )));
         }
 
+        if !sharing::has_level(&self.pool, "workout_repeats", *id, *user_id, Level::Editor).await {
+            return Err(Status::permission_denied(format!(
+                "editor permission required for workout_repeat #{}",
+                id
+            )));
+        }
+
         let mut query_builder = QueryBuilder::new("workout_repeats");
 
         if let Some(repeats) = repeats {
@@ -201,11 +252,22 @@ impl proto::santa_cruz::workout_repeat_service_server::WorkoutRepeatService
 
         let sql = query_builder.update_query();
 
+        let before = audit::snapshot(&self.pool, "workout_repeats", *id).await;
+
         sqlx::query_with(sql.0.as_str(), sql.1)
             .execute(&self.pool)
             .await
             .expect("update_workout_repeat error");
 
+        audit::record(
+            &self.pool,
+            request.extensions(),
+            "workout_repeats",
+            *id,
+            before,
+        )
+        .await;
+
         self.return_workout_repeat_by_id(*id, *user_id).await
     }
 
@@ -213,20 +275,42 @@ impl proto::santa_cruz::workout_repeat_service_server::WorkoutRepeatService
         &self,
         request: Request<DeleteWorkoutRepeatRequest>,
     ) -> Result<Response<DeleteWorkoutRepeatResponse>, Status> {
-        let MeExtension { user_id } = &request.extensions().get::<MeExtension>().unwrap();
+        let MeExtension { user_id, .. } = &request.extensions().get::<MeExtension>().unwrap();
         let DeleteWorkoutRepeatRequest { id } = &request.get_ref();
 
         let mut query_builder = QueryBuilder::new("workout_repeats");
-        query_builder.where_raw("((permissions ->> CAST(${index} as text))::integer > 0 OR (permissions ->> '0')::integer > 0)", user_id);
+        query_builder.where_permission(Level::Owner, *user_id);
         query_builder.where_eq("id", id);
 
         let sql = query_builder.delete_query();
 
-        sqlx::query_with(sql.0.as_str(), sql.1)
+        let before = audit::snapshot(&self.pool, "workout_repeats", *id).await;
+
+        let result = sqlx::query_with(sql.0.as_str(), sql.1)
             .execute(&self.pool)
             .await
             .expect("update_workout_repeat error");
 
+        if result.rows_affected() == 0 {
+            return Err(sharing::write_denied(
+                &self.pool,
+                "workout_repeats",
+                *id,
+                *user_id,
+                Level::Owner,
+            )
+            .await);
+        }
+
+        audit::record(
+            &self.pool,
+            request.extensions(),
+            "workout_repeats",
+            *id,
+            before,
+        )
+        .await;
+
         Ok(Response::new(DeleteWorkoutRepeatResponse {}))
     }
 
@@ -234,8 +318,19 @@ impl proto::santa_cruz::workout_repeat_service_server::WorkoutRepeatService
         &self,
         request: Request<GetWorkoutRepeatsRequest>,
     ) -> Result<Response<GetWorkoutRepeatsResponse>, Status> {
-        let MeExtension { user_id } = request.extensions().get::<MeExtension>().unwrap();
-        let GetWorkoutRepeatsRequest { workout_set_id } = request.get_ref();
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+        let GetWorkoutRepeatsRequest {
+            workout_set_id,
+            page_size,
+            page_token,
+            sort,
+            filter,
+        } = request.get_ref();
+
+        let page_size = pagination::page_size(*page_size);
+        let cursor = pagination::Cursor::decode(page_token).map_err(Status::invalid_argument)?;
+
+        let order = sorting::parse(sort, SORTABLE).map_err(Status::invalid_argument)?;
 
         let mut query_builder = QueryBuilder::new("workout_repeats");
         query_builder.fields(vec![
@@ -248,7 +343,9 @@ impl proto::santa_cruz::workout_repeat_service_server::WorkoutRepeatService
             "weight",
             "time",
         ]);
-        query_builder.where_raw("((permissions ->> CAST(${index} as text))::integer > 0 OR (permissions ->> '0')::integer > 0)", user_id);
+        query_builder.where_permission(Level::Viewer, *user_id);
+        filtering::apply(&mut query_builder, filter.as_ref(), FILTERABLE)
+            .map_err(Status::invalid_argument)?;
 
         if let Some(id_query) = workout_set_id {
             match id_query.clone().value.unwrap() {
@@ -262,16 +359,25 @@ impl proto::santa_cruz::workout_repeat_service_server::WorkoutRepeatService
             }
         }
 
+        query_builder.order_by(order);
+        query_builder
+            .paginate(page_size, cursor.as_ref(), SORTABLE)
+            .map_err(Status::invalid_argument)?;
+
         let sql = query_builder.select_query();
 
-        let workout_repeats = sqlx::query_with(sql.0.as_str(), sql.1)
+        let mut rows = sqlx::query_with(sql.0.as_str(), sql.1)
             .fetch_all(&self.pool)
             .await
-            .expect("get_workout_repeat_by_id error")
-            .into_iter()
-            .map(|row| row.into())
-            .collect();
+            .map_err(errors::internal)?;
+
+        let next_page_token =
+            pagination::next_page_token(&mut rows, page_size, pagination::row_cursor);
+        let workout_repeats = rows.into_iter().map(|row| row.into()).collect();
 
-        Ok(Response::new(GetWorkoutRepeatsResponse { workout_repeats }))
+        Ok(Response::new(GetWorkoutRepeatsResponse {
+            workout_repeats,
+            next_page_token,
+        }))
     }
 }
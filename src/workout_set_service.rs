@@ -1,12 +1,14 @@
-use std::collections::HashMap;
-
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::types::Json;
-use sqlx::{postgres::PgArguments, Arguments, PgPool};
+use sqlx::{postgres::PgArguments, Arguments, FromRow, PgPool};
 use tonic::{Request, Response, Status};
 
+use crate::audit;
+use crate::errors;
+use crate::filtering;
 use crate::me_extension::MeExtension;
+use crate::pagination;
 use crate::proto::proto;
 use crate::proto::proto::santa_cruz;
 use crate::proto::proto::santa_cruz::workout_set_type::Type;
@@ -15,13 +17,33 @@ use crate::proto::proto::santa_cruz::{
     GetWorkoutSetRequest, GetWorkoutSetsRequest, GetWorkoutSetsResponse, UpdateWorkoutSetRequest,
     WorkoutSet,
 };
-use crate::WorkoutService;
+use crate::query_builder::QueryBuilder;
+use crate::queryable::FieldKind;
+use crate::sharing;
+use crate::sharing::Level;
+use crate::sorting;
+use crate::sorting::{Direction, Order};
+
+const SORTABLE: &[(&str, FieldKind)] = &[
+    ("position", FieldKind::Int),
+    ("created_at", FieldKind::Timestamp),
+    ("updated_at", FieldKind::Timestamp),
+];
+
+const FILTERABLE: &[(&str, FieldKind)] = &[
+    ("id", FieldKind::Int),
+    ("created_at", FieldKind::Timestamp),
+    ("updated_at", FieldKind::Timestamp),
+    ("workout_id", FieldKind::Int),
+    ("position", FieldKind::Int),
+    ("comment", FieldKind::Text),
+];
 
 pub struct WorkoutSetService {
     pool: PgPool,
 }
 
-type WorkoutSetRow = (
+pub type WorkoutSetRow = (
     i32,
     i32,
     i32,
@@ -98,19 +120,20 @@ impl WorkoutSetService {
     }
 
     pub async fn get_workout_set_by_id(pool: &PgPool, id: i32, user_id: i32) -> Option<WorkoutSet> {
-        sqlx::query_as::<_, WorkoutSetRow>(
+        sqlx::query_as::<_, WorkoutSetRow>(&format!(
             r#"
                     SELECT id, workout_id, position, type, comment, created_at, updated_at
                     FROM workout_sets
-                    WHERE id = $1 AND ((permissions ->> CAST($2 as text))::integer > 0 OR (permissions ->> '0')::integer > 0)
+                    WHERE id = $1 AND {}
                 "#,
-        )
-            .bind(id)
-            .bind(user_id)
-            .fetch_one(pool)
-            .await
-            .map(|row| row.into())
-            .ok()
+            sharing::permission_condition("workout_sets", Level::Viewer, "$2")
+        ))
+        .bind(id)
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+        .map(|row| row.into())
+        .ok()
     }
 
     pub async fn return_workout_set_by_id(
@@ -134,7 +157,7 @@ impl proto::santa_cruz::workout_set_service_server::WorkoutSetService for Workou
         &self,
         request: Request<GetWorkoutSetRequest>,
     ) -> Result<Response<WorkoutSet>, Status> {
-        let MeExtension { user_id } = request.extensions().get::<MeExtension>().unwrap();
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
         let GetWorkoutSetRequest { id } = request.get_ref();
 
         self.return_workout_set_by_id(*id, *user_id).await
@@ -144,7 +167,7 @@ impl proto::santa_cruz::workout_set_service_server::WorkoutSetService for Workou
         &self,
         request: Request<CreateWorkoutSetRequest>,
     ) -> Result<Response<WorkoutSet>, Status> {
-        let MeExtension { user_id } = &request.extensions().get::<MeExtension>().unwrap();
+        let MeExtension { user_id, .. } = &request.extensions().get::<MeExtension>().unwrap();
 
         let CreateWorkoutSetRequest {
             workout_id,
@@ -152,17 +175,17 @@ impl proto::santa_cruz::workout_set_service_server::WorkoutSetService for Workou
             r#type,
         } = &request.get_ref();
 
-        let workout = WorkoutService::get_workout_by_id(&self.pool, *workout_id, *user_id).await;
-
-        if workout.is_none() {
-            return Err(Status::permission_denied(format!(
-                "permissions not found for workout #{}",
-                workout_id.to_string()
-            )));
-        }
+        let mut tx = self.pool.begin().await.expect("create_workout_set error");
 
-        let mut permissions = HashMap::new();
-        permissions.insert(user_id, 2);
+        let permissions =
+            sharing::lock_permissions(&mut tx, "workouts", *workout_id, *user_id, Level::Editor)
+                .await
+                .ok_or_else(|| {
+                    Status::permission_denied(format!(
+                        "permissions not found for workout #{}",
+                        workout_id
+                    ))
+                })?;
 
         let (id, ): (i32, ) = sqlx::query_as(
             r#"INSERT INTO workout_sets ( workout_id, position, type, permissions ) VALUES ( $1 , $2, $3, $4 ) RETURNING id"#,
@@ -171,10 +194,14 @@ impl proto::santa_cruz::workout_set_service_server::WorkoutSetService for Workou
             .bind(position)
             .bind(Json::from(r#type.clone().unwrap()))
             .bind(Json(permissions))
-            .fetch_one(&self.pool)
+            .fetch_one(&mut tx)
             .await
             .expect("create_workout_set error");
 
+        tx.commit().await.expect("create_workout_set error");
+
+        audit::record(&self.pool, request.extensions(), "workout_sets", id, None).await;
+
         self.return_workout_set_by_id(id, *user_id).await
     }
 
@@ -182,7 +209,7 @@ impl proto::santa_cruz::workout_set_service_server::WorkoutSetService for Workou
         &self,
         request: Request<UpdateWorkoutSetRequest>,
     ) -> Result<Response<WorkoutSet>, Status> {
-        let MeExtension { user_id } = &request.extensions().get::<MeExtension>().unwrap();
+        let MeExtension { user_id, .. } = &request.extensions().get::<MeExtension>().unwrap();
         let UpdateWorkoutSetRequest {
             id,
             comment,
@@ -199,6 +226,13 @@ impl proto::santa_cruz::workout_set_service_server::WorkoutSetService for Workou
             )));
         }
 
+        if !sharing::has_level(&self.pool, "workout_sets", *id, *user_id, Level::Editor).await {
+            return Err(Status::permission_denied(format!(
+                "editor permission required for workout_set #{}",
+                id
+            )));
+        }
+
         let mut arguments = PgArguments::default();
 
         let mut params = vec![];
@@ -239,11 +273,22 @@ impl proto::santa_cruz::workout_set_service_server::WorkoutSetService for Workou
 
         arguments.add(id);
 
+        let before = audit::snapshot(&self.pool, "workout_sets", *id).await;
+
         sqlx::query_with(&*query, arguments)
             .execute(&self.pool)
             .await
             .expect("update_workout_set error");
 
+        audit::record(
+            &self.pool,
+            request.extensions(),
+            "workout_sets",
+            *id,
+            before,
+        )
+        .await;
+
         self.return_workout_set_by_id(*id, *user_id).await
     }
 
@@ -251,15 +296,40 @@ impl proto::santa_cruz::workout_set_service_server::WorkoutSetService for Workou
         &self,
         request: Request<DeleteWorkoutSetRequest>,
     ) -> Result<Response<DeleteWorkoutSetResponse>, Status> {
-        let MeExtension { user_id } = request.extensions().get::<MeExtension>().unwrap();
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
         let DeleteWorkoutSetRequest { id } = &request.get_ref();
 
-        sqlx::query(r#"DELETE FROM workout_sets WHERE id = $1 AND (permissions ->> CAST($2 as text))::integer > 1"#)
-            .bind(id)
-            .bind(user_id)
-            .execute(&self.pool)
-            .await
-            .expect("update_workout_set error");
+        let before = audit::snapshot(&self.pool, "workout_sets", *id).await;
+
+        let result = sqlx::query(&format!(
+            r#"DELETE FROM workout_sets WHERE id = $1 AND {}"#,
+            sharing::permission_condition("workout_sets", Level::Owner, "$2")
+        ))
+        .bind(id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await
+        .expect("update_workout_set error");
+
+        if result.rows_affected() == 0 {
+            return Err(sharing::write_denied(
+                &self.pool,
+                "workout_sets",
+                *id,
+                *user_id,
+                Level::Owner,
+            )
+            .await);
+        }
+
+        audit::record(
+            &self.pool,
+            request.extensions(),
+            "workout_sets",
+            *id,
+            before,
+        )
+        .await;
 
         Ok(Response::new(DeleteWorkoutSetResponse {}))
     }
@@ -268,20 +338,61 @@ impl proto::santa_cruz::workout_set_service_server::WorkoutSetService for Workou
         &self,
         request: Request<GetWorkoutSetsRequest>,
     ) -> Result<Response<GetWorkoutSetsResponse>, Status> {
-        let MeExtension { user_id } = request.extensions().get::<MeExtension>().unwrap();
-        let GetWorkoutSetsRequest { workout_id } = &request.get_ref();
+        let MeExtension { user_id, .. } = request.extensions().get::<MeExtension>().unwrap();
+        let GetWorkoutSetsRequest {
+            workout_id,
+            page_size,
+            page_token,
+            sort,
+            filter,
+        } = &request.get_ref();
 
-        let rows = sqlx::query_as::<_, WorkoutSetRow>(
-            r#"SELECT id, workout_id, position, type, comment, created_at, updated_at FROM workout_sets WHERE workout_id = $1 AND ((permissions ->> CAST($2 as text))::integer > 0 OR (permissions ->> '0')::integer > 0)"#,
-        )
-            .bind(workout_id)
-            .bind(user_id)
-            .fetch_all(&self.pool)
-            .await
-            .expect("get_workout_sets error");
+        let page_size = pagination::page_size(*page_size);
+        let cursor = pagination::Cursor::decode(page_token).map_err(Status::invalid_argument)?;
 
-        let workout_sets = rows.into_iter().map(|row| row.into()).collect();
+        let mut order = sorting::parse(sort, SORTABLE).map_err(Status::invalid_argument)?;
 
-        Ok(Response::new(GetWorkoutSetsResponse { workout_sets }))
+        if order.is_empty() {
+            order.push(Order::new("position", Direction::Asc));
+        }
+
+        let mut query_builder = QueryBuilder::new("workout_sets");
+        query_builder.fields(vec![
+            "id",
+            "workout_id",
+            "position",
+            "type",
+            "comment",
+            "created_at",
+            "updated_at",
+        ]);
+        query_builder.where_permission(Level::Viewer, *user_id);
+        query_builder.where_eq("workout_id", *workout_id);
+        filtering::apply(&mut query_builder, filter.as_ref(), FILTERABLE)
+            .map_err(Status::invalid_argument)?;
+        query_builder.order_by(order);
+        query_builder
+            .paginate(page_size, cursor.as_ref(), SORTABLE)
+            .map_err(Status::invalid_argument)?;
+
+        let sql = query_builder.select_query();
+
+        let mut rows = sqlx::query_with(sql.0.as_str(), sql.1)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(errors::internal)?;
+
+        let next_page_token =
+            pagination::next_page_token(&mut rows, page_size, pagination::row_cursor);
+        let workout_sets = rows
+            .iter()
+            .map(|row| WorkoutSetRow::from_row(row).map(|row| row.into()))
+            .collect::<Result<Vec<WorkoutSet>, _>>()
+            .map_err(errors::internal)?;
+
+        Ok(Response::new(GetWorkoutSetsResponse {
+            workout_sets,
+            next_page_token,
+        }))
     }
 }
@@ -0,0 +1,201 @@
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgRow;
+use sqlx::types::Json;
+use sqlx::{PgPool, Row};
+use tonic::Status;
+
+use crate::errors;
+use crate::proto::proto::santa_cruz::{
+    Exercise, Workout, WorkoutRepeat, WorkoutSet, WorkoutTree, WorkoutTreeSet,
+};
+use crate::query_builder::{Condition, QueryBuilder};
+use crate::sharing::Level;
+use crate::sorting::{Direction, Order};
+use crate::workout_set_service::{WorkoutSetRow, WorkoutSetType};
+use crate::Queryable;
+
+const SET_FIELDS: &[&str] = &[
+    "id",
+    "workout_id",
+    "position",
+    "type",
+    "comment",
+    "created_at",
+    "updated_at",
+];
+
+const REPEAT_FIELDS: &[&str] = &[
+    "id",
+    "created_at",
+    "updated_at",
+    "workout_set_id",
+    "exercise_id",
+    "repeats",
+    "weight",
+    "time",
+];
+
+const EXERCISE_FIELDS: &[&str] = &["id", "created_at", "updated_at", "name", "description"];
+
+/// `table.field AS prefix_field` for each of `fields`.
+fn prefixed(table: &str, prefix: &str, fields: &[&str]) -> Vec<String> {
+    fields
+        .iter()
+        .map(|field| format!("{}.{} AS {}_{}", table, field, prefix, field))
+        .collect()
+}
+
+/// Loads workout `id` with its sets, their repeats and the exercises they reference in a
+/// single query. Children the user can't view are left out, as in their own listings, and a
+/// workout they can't view is not found.
+pub async fn get_workout_tree(pool: &PgPool, id: i32, user_id: i32) -> Result<WorkoutTree, Status> {
+    let mut query_builder = QueryBuilder::new("workouts");
+    query_builder.fields(
+        Workout::fields()
+            .into_iter()
+            .map(|field| format!("workouts.{0} AS {0}", field))
+            .collect(),
+    );
+    query_builder.fields(prefixed("workout_sets", "set", SET_FIELDS));
+    query_builder.fields(prefixed("workout_repeats", "repeat", REPEAT_FIELDS));
+    query_builder.fields(prefixed("exercises", "exercise", EXERCISE_FIELDS));
+
+    let set_permission = query_builder.permission("workout_sets", Level::Viewer, user_id);
+    query_builder.left_join(
+        "workout_sets",
+        Condition::And(vec![
+            Condition::Sql("workout_sets.workout_id = workouts.id".to_string()),
+            set_permission,
+        ]),
+    );
+
+    let repeat_permission = query_builder.permission("workout_repeats", Level::Viewer, user_id);
+    query_builder.left_join(
+        "workout_repeats",
+        Condition::And(vec![
+            Condition::Sql("workout_repeats.workout_set_id = workout_sets.id".to_string()),
+            repeat_permission,
+        ]),
+    );
+
+    let exercise_permission = query_builder.permission("exercises", Level::Viewer, user_id);
+    query_builder.left_join(
+        "exercises",
+        Condition::And(vec![
+            Condition::Or(vec![
+                Condition::Sql("exercises.id = workout_repeats.exercise_id".to_string()),
+                Condition::Sql(
+                    "exercises.id = (workout_sets.type->>'exercise_id')::int".to_string(),
+                ),
+            ]),
+            exercise_permission,
+        ]),
+    );
+
+    query_builder.where_eq("workouts.id", id);
+    query_builder.where_permission(Level::Viewer, user_id);
+    query_builder.order_by(vec![
+        Order::new("workout_sets.position", Direction::Asc),
+        Order::new("workout_sets.id", Direction::Asc),
+        Order::new("workout_repeats.id", Direction::Asc),
+        Order::new("exercises.id", Direction::Asc),
+    ]);
+
+    let sql = query_builder.select_query();
+
+    let mut rows = sqlx::query_with(sql.0.as_str(), sql.1)
+        .fetch_all(pool)
+        .await
+        .map_err(errors::internal)?;
+
+    let mut workout_sets: Vec<WorkoutTreeSet> = vec![];
+    let mut exercises: Vec<Exercise> = vec![];
+
+    // Rows are the product of the joins, so sets and repeats repeat on consecutive rows.
+    for row in &rows {
+        if let Some(set_id) = row.get::<Option<i32>, _>("set_id") {
+            if workout_sets
+                .last()
+                .and_then(|set| set.workout_set.as_ref())
+                .map(|set| set.id)
+                != Some(set_id)
+            {
+                workout_sets.push(WorkoutTreeSet {
+                    workout_set: Some(workout_set(row)),
+                    workout_repeats: vec![],
+                });
+            }
+        }
+
+        if let (Some(set), Some(repeat_id)) = (
+            workout_sets.last_mut(),
+            row.get::<Option<i32>, _>("repeat_id"),
+        ) {
+            if set.workout_repeats.last().map(|repeat| repeat.id) != Some(repeat_id) {
+                set.workout_repeats.push(workout_repeat(row));
+            }
+        }
+
+        if let Some(exercise_id) = row.get::<Option<i32>, _>("exercise_id") {
+            if !exercises.iter().any(|exercise| exercise.id == exercise_id) {
+                exercises.push(exercise(row));
+            }
+        }
+    }
+
+    exercises.sort_by_key(|exercise| exercise.id);
+
+    match rows.is_empty() {
+        true => Err(Status::not_found(format!("object #{} not found", id))),
+        false => Ok(WorkoutTree {
+            workout: Some(Workout::from(rows.swap_remove(0))),
+            workout_sets,
+            exercises,
+        }),
+    }
+}
+
+fn workout_set(row: &PgRow) -> WorkoutSet {
+    let workout_set: WorkoutSetRow = (
+        row.get("set_id"),
+        row.get("set_workout_id"),
+        row.get("set_position"),
+        row.get::<Json<WorkoutSetType>, _>("set_type"),
+        row.get("set_comment"),
+        row.get("set_created_at"),
+        row.get("set_updated_at"),
+    );
+
+    workout_set.into()
+}
+
+fn workout_repeat(row: &PgRow) -> WorkoutRepeat {
+    WorkoutRepeat {
+        id: row.get::<i32, _>("repeat_id"),
+        created_at: row
+            .get::<DateTime<Utc>, _>("repeat_created_at")
+            .to_rfc3339(),
+        updated_at: row
+            .get::<DateTime<Utc>, _>("repeat_updated_at")
+            .to_rfc3339(),
+        workout_set_id: row.get::<i32, _>("repeat_workout_set_id"),
+        exercise_id: row.get::<i32, _>("repeat_exercise_id"),
+        repeats: row.get::<i32, _>("repeat_repeats"),
+        weight: row.get::<Option<f64>, _>("repeat_weight"),
+        time: row.get::<Option<f64>, _>("repeat_time"),
+    }
+}
+
+fn exercise(row: &PgRow) -> Exercise {
+    Exercise {
+        id: row.get::<i32, _>("exercise_id"),
+        created_at: row
+            .get::<DateTime<Utc>, _>("exercise_created_at")
+            .to_rfc3339(),
+        updated_at: row
+            .get::<DateTime<Utc>, _>("exercise_updated_at")
+            .to_rfc3339(),
+        name: row.get::<String, _>("exercise_name"),
+        description: row.get::<String, _>("exercise_description"),
+    }
+}